@@ -0,0 +1,103 @@
+//! Compare the allocation-free neighbour iterators on `Coordinate` against the old versions that
+//! built a fresh `Vec` every time. There's no benchmarking crate here, so this just times a few
+//! runs over a big grid and prints the best one.
+//!
+//! ```shell
+//! $ cargo bench --bench neighbours
+//! ```
+
+use advent_of_code_2021::data::Coordinate;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const MAX_POINT: Coordinate = Coordinate(1000, 1000);
+const RUNS: usize = 10;
+
+/// The old `Coordinate::manhattan_neighbours`.
+fn manhattan_neighbours_vec(
+    point: Coordinate,
+    max_point: Coordinate,
+) -> impl Iterator<Item = Coordinate> {
+    let Coordinate(x, y) = point;
+    let Coordinate(max_x, max_y) = max_point;
+
+    let mut output = Vec::new();
+    if x > 0 {
+        output.push(Coordinate(x - 1, y));
+    }
+    if x + 1 < max_x {
+        output.push(Coordinate(x + 1, y));
+    }
+    if y > 0 {
+        output.push(Coordinate(x, y - 1));
+    }
+    if y + 1 < max_y {
+        output.push(Coordinate(x, y + 1));
+    }
+
+    output.into_iter()
+}
+
+/// The old `Coordinate::all_neighbours`.
+fn all_neighbours_vec(
+    point: Coordinate,
+    max_point: Coordinate,
+) -> impl Iterator<Item = Coordinate> {
+    let Coordinate(x, y) = point;
+    let Coordinate(max_x, max_y) = max_point;
+
+    let mut output = Vec::new();
+
+    for dx in -1..=1 {
+        if x == 0 && dx == -1 || (x + 1 == max_x && dx == 1) {
+            continue;
+        }
+
+        for dy in -1..=1 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+
+            if y == 0 && dy == -1 || (y + 1 == max_y && dy == 1) {
+                continue;
+            }
+
+            output.push(Coordinate(
+                (x as isize + dx) as usize,
+                (y as isize + dy) as usize,
+            ))
+        }
+    }
+
+    output.into_iter()
+}
+
+/// Sum up the neighbours of every point in the grid, and time how long the fastest run took.
+fn bench<I: Iterator<Item = Coordinate>>(
+    name: &str,
+    neighbours: impl Fn(Coordinate, Coordinate) -> I,
+) {
+    let best = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let total = Coordinate::iter(MAX_POINT)
+                .flat_map(|point| neighbours(black_box(point), MAX_POINT))
+                .map(|Coordinate(x, y)| x + y)
+                .sum::<usize>();
+            black_box(total);
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    println!("{:<24} {:>10.2?}", name, best);
+}
+
+fn main() {
+    bench("manhattan (vec)", manhattan_neighbours_vec);
+    bench("manhattan (no alloc)", Coordinate::manhattan_neighbours);
+    bench("all (vec)", all_neighbours_vec);
+    bench("all (no alloc)", Coordinate::all_neighbours);
+}
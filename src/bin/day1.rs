@@ -14,10 +14,11 @@ struct Args {
 fn main() -> eyre::Result<()> {
     let args = util::setup::<Args>()?;
 
-    let lines = parsing::line_separated::<usize, _>(&args.file)?;
-
-    println!("Part one: {}", part_one(lines.iter().copied()));
-    println!("Part two: {}", part_two(lines.iter().copied()));
+    // Read the file once for each part, so that even huge inputs never have to fit in memory
+    let lines = parsing::line_separated_lazy::<usize, _>(&args.file)?;
+    println!("Part one: {}", itertools::process_results(lines, |it| part_one(it))?);
+    let lines = parsing::line_separated_lazy::<usize, _>(&args.file)?;
+    println!("Part two: {}", itertools::process_results(lines, |it| part_two(it))?);
 
     Ok(())
 }
@@ -1,13 +1,10 @@
 use advent_of_code_2021::{
-    parsing,
-    tools::{MoreItertools, StringTools},
+    graph::{Graph, Path},
+    parsing::{self, Keywords},
+    tools::StringTools,
     util,
 };
-use std::{
-    collections::{HashMap, HashSet},
-    path::PathBuf,
-    str::FromStr,
-};
+use std::{path::PathBuf, str::FromStr};
 
 use structopt::StructOpt;
 
@@ -36,13 +33,25 @@ impl FromStr for Vertex {
             "" => eyre::bail!("No empty strings allowed"),
             _ if s.chars().all(|c| c.is_ascii_uppercase()) => Vertex::Big(s.into()),
             _ if s.chars().all(|c| c.is_ascii_lowercase()) => Vertex::Small(s.into()),
-            _ => eyre::bail!("Invalid node string: {:?}", s),
+            _ => return Err(mixed_case_error(s)),
         };
 
         Ok(output)
     }
 }
 
+/// Mixed case is never a valid cave name, but it might be a mistyped start or end, so the keywords
+/// are only there to suggest which one it looks like. They can't match, since they're lower case.
+fn mixed_case_error(s: &str) -> eyre::Report {
+    const SPECIAL: Keywords<Vertex> =
+        Keywords::new(&[("start", Vertex::Start), ("end", Vertex::End)]);
+
+    SPECIAL.parse(s).unwrap_err().wrap_err(format!(
+        "Invalid node string {:?}, caves must be all upper or lower case",
+        s
+    ))
+}
+
 #[derive(Debug)]
 struct Edge {
     from: Vertex,
@@ -58,57 +67,22 @@ impl FromStr for Edge {
     }
 }
 
-#[derive(Debug)]
-struct Graph {
-    edges: HashMap<Vertex, Vec<Vertex>>,
-}
+type CaveSystem = Graph<Vertex>;
 
-impl Graph {
-    fn new(input: impl Iterator<Item = Edge>) -> eyre::Result<Self> {
-        let mut edges = HashMap::new();
-
-        let mut seen_start = false;
-        let mut seen_end = false;
-
-        for Edge { from, to } in input {
-            match &from {
-                Vertex::Start => seen_start = true,
-                Vertex::End => seen_end = true,
-                _ => (),
-            };
-            match &to {
-                Vertex::Start => seen_start = true,
-                Vertex::End => seen_end = true,
-                _ => (),
-            };
-            edges
-                .entry(from.clone())
-                .or_insert_with(Vec::new)
-                .push(to.clone());
-            edges.entry(to).or_insert_with(Vec::new).push(from);
-        }
-
-        eyre::ensure!(seen_start, "Did not see the start node");
-        eyre::ensure!(seen_end, "Did not see the end node");
-
-        Ok(Graph { edges })
-    }
-}
-
-impl FromStr for Graph {
-    type Err = eyre::Report;
+fn build_caves(input: impl IntoIterator<Item = Edge>) -> eyre::Result<CaveSystem> {
+    let edges = input.into_iter().map(|Edge { from, to }| (from, to));
+    let graph = Graph::undirected_from_edges(edges);
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let edges = s.lines_good().parsed().collect::<Result<Vec<_>, _>>()?;
+    eyre::ensure!(graph.contains(&Vertex::Start), "Did not see the start node");
+    eyre::ensure!(graph.contains(&Vertex::End), "Did not see the end node");
 
-        Graph::new(edges.into_iter())
-    }
+    Ok(graph)
 }
 
 fn main() -> eyre::Result<()> {
     let args = util::setup::<Args>()?;
     let edges = parsing::line_separated::<Edge, _>(args.file)?;
-    let graph = Graph::new(edges.into_iter())?;
+    let graph = build_caves(edges)?;
 
     println!("Part one: {}", part_one(&graph));
     println!("Part two: {}", part_two(&graph));
@@ -116,49 +90,45 @@ fn main() -> eyre::Result<()> {
     Ok(())
 }
 
-fn paths_to_end(
-    graph: &Graph,
-    vertex: &Vertex,
-    seen: &HashSet<&str>,
-    has_doubled_small_yet: bool,
-) -> usize {
-    graph
-        .edges
-        .get(vertex)
-        .iter()
-        .flat_map(|x| x.iter())
-        .map(|next| match next {
-            Vertex::End => 1,
-            Vertex::Start => 0,
-            Vertex::Big(_) => {
-                let output = paths_to_end(graph, next, seen, has_doubled_small_yet);
-                output
-            }
-            Vertex::Small(id) => {
-                if !seen.contains(id.as_str()) {
-                    let mut new_db = seen.clone();
-                    new_db.insert(id);
-                    paths_to_end(graph, next, &new_db, has_doubled_small_yet)
-                } else if !has_doubled_small_yet {
-                    paths_to_end(graph, next, seen, true)
-                } else {
-                    0
-                }
-            }
-        })
-        .sum()
+/// Big caves can be visited as often as you like, but small caves only once, and there's no going
+/// back to the start.
+fn small_caves_once(path: &Path<Vertex>, next: &Vertex) -> bool {
+    match next {
+        Vertex::Start => false,
+        Vertex::Small(_) => path.count(next) == 0,
+        Vertex::Big(_) | Vertex::End => true,
+    }
+}
+
+/// The same as `small_caves_once`, but one small cave (and only one) can be visited twice.
+fn one_small_cave_twice(path: &Path<Vertex>, next: &Vertex) -> bool {
+    match next {
+        Vertex::Small(_) if path.count(next) == 1 => !path
+            .vertices()
+            .any(|vertex| matches!(vertex, Vertex::Small(_)) && path.count(vertex) > 1),
+        other => small_caves_once(path, other),
+    }
 }
 
-fn part_one(input: &Graph) -> usize {
-    paths_to_end(input, &Vertex::Start, &HashSet::new(), true)
+fn part_one(input: &CaveSystem) -> usize {
+    input.count_paths(&Vertex::Start, &Vertex::End, small_caves_once)
 }
 
-fn part_two(input: &Graph) -> usize {
-    paths_to_end(input, &Vertex::Start, &HashSet::new(), false)
+fn part_two(input: &CaveSystem) -> usize {
+    input.count_paths(&Vertex::Start, &Vertex::End, one_small_cave_twice)
 }
 
 #[cfg(test)]
 mod test {
+    use super::CaveSystem;
+    use advent_of_code_2021::tools::{MoreItertools, StringTools};
+
+    fn parse_caves(s: &str) -> eyre::Result<CaveSystem> {
+        let edges = s.lines_good().parsed().collect::<Result<Vec<_>, _>>()?;
+
+        super::build_caves(edges)
+    }
+
     const GRAPH_ONE: &'static str = r"
         start-A
         start-b
@@ -203,17 +173,28 @@ mod test {
         start-RW
     ";
 
+    #[test]
+    fn suggests_start_and_end() {
+        let error = "Start".parse::<super::Vertex>().unwrap_err();
+        assert_eq!(
+            "Invalid node string \"Start\", caves must be all upper or lower case",
+            error.to_string()
+        );
+        assert!(format!("{:?}", error).contains("did you mean \"start\"?"));
+        assert_eq!(super::Vertex::Big("END".into()), "END".parse().unwrap());
+    }
+
     #[test]
     fn example_part_one() {
-        assert_eq!(10, super::part_one(&GRAPH_ONE.parse().unwrap()));
-        assert_eq!(19, super::part_one(&GRAPH_TWO.parse().unwrap()));
-        assert_eq!(226, super::part_one(&GRAPH_THREE.parse().unwrap()));
+        assert_eq!(10, super::part_one(&parse_caves(GRAPH_ONE).unwrap()));
+        assert_eq!(19, super::part_one(&parse_caves(GRAPH_TWO).unwrap()));
+        assert_eq!(226, super::part_one(&parse_caves(GRAPH_THREE).unwrap()));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(36, super::part_two(&GRAPH_ONE.parse().unwrap()));
-        assert_eq!(103, super::part_two(&GRAPH_TWO.parse().unwrap()));
-        assert_eq!(3509, super::part_two(&GRAPH_THREE.parse().unwrap()));
+        assert_eq!(36, super::part_two(&parse_caves(GRAPH_ONE).unwrap()));
+        assert_eq!(103, super::part_two(&parse_caves(GRAPH_TWO).unwrap()));
+        assert_eq!(3509, super::part_two(&parse_caves(GRAPH_THREE).unwrap()));
     }
 }
@@ -1,10 +1,14 @@
 use advent_of_code_2021::{
-    data::Coordinate,
+    data::{Coordinate, Grid},
+    ocr,
+    parsing::{self, Keywords},
+    render,
     tools::{MoreItertools, StringTools},
     util,
 };
-use std::{collections::HashSet, fs, path::PathBuf, str::FromStr};
+use std::{collections::HashSet, path::PathBuf, str::FromStr};
 
+use eyre::WrapErr;
 use itertools::Itertools;
 use structopt::StructOpt;
 
@@ -58,23 +62,21 @@ impl FromStr for Fold {
         let (dir, val) = tail
             .split_once('=')
             .ok_or_else(|| eyre::format_err!("Missing ="))?;
+        const AXES: Keywords<fn(usize) -> Fold> = Keywords::new(&[("x", Fold::X), ("y", Fold::Y)]);
+
+        let axis = AXES.parse(dir)?;
         let val = val.parse()?;
-        let output = match dir {
-            "x" => Fold::X(val),
-            "y" => Fold::Y(val),
-            _ => eyre::bail!("Invalid axis to fold along"),
-        };
-        Ok(output)
+        Ok(axis(val))
     }
 }
 
 fn main() -> eyre::Result<()> {
     let args = util::setup::<Args>()?;
-    let input = fs::read_to_string(args.file)?;
+    let input = parsing::read_input(args.file)?;
     let input = input.parse()?;
 
     println!("Part one: {}", part_one(&input));
-    println!("Part two: {:#?}", part_two(&input));
+    println!("Part two: {}", part_two(&input)?);
 
     Ok(())
 }
@@ -133,7 +135,8 @@ fn part_one(input: &Manual) -> usize {
         .count()
 }
 
-fn part_two(input: &Manual) -> Vec<String> {
+/// Fold the paper up, and see which cells have a dot on them.
+fn fold_paper(input: &Manual) -> Grid<bool> {
     let all_points = input
         .points()
         .map(|coord| input.folds().fold(coord, |coord2, fold| fold.fold(coord2)))
@@ -142,24 +145,23 @@ fn part_two(input: &Manual) -> Vec<String> {
     let max_x = input.folds().filter_map(Fold::get_x).last().unwrap();
     let max_y = input.folds().filter_map(Fold::get_y).last().unwrap();
 
-    (0..max_y)
-        .map(|y| {
-            (0..max_x)
-                .map(|x| {
-                    if all_points.contains(&Coordinate(x, y)) {
-                        '#'
-                    } else {
-                        '.'
-                    }
-                })
-                .collect::<String>()
-        })
-        .collect::<Vec<_>>()
+    Grid::from_fn(max_x, max_y, |coord| all_points.contains(&coord))
+}
+
+fn draw(paper: &Grid<bool>) -> String {
+    render::ascii(paper, |&dot| if dot { '#' } else { '.' })
+}
+
+fn part_two(input: &Manual) -> eyre::Result<String> {
+    let paper = fold_paper(input);
+    ocr::read(&paper)
+        .wrap_err_with(|| format!("Couldn't read the folded paper:\n{}", draw(&paper)))
 }
 
 #[cfg(test)]
 mod test {
     use super::Manual;
+    use advent_of_code_2021::{data::Coordinate, tools::StringTools};
 
     const EXAMPLE_INPUT: &'static str = r"
         6,10
@@ -186,7 +188,20 @@ mod test {
     ";
 
     fn get_input() -> Manual {
-        EXAMPLE_INPUT.parse().unwrap()
+        EXAMPLE_INPUT.dedent().parse().unwrap()
+    }
+
+    #[test]
+    fn example_keeps_everything() {
+        // The blank line between the points and the folds has to survive the dedent
+        let input = get_input();
+        assert_eq!(18, input.points().count());
+        assert_eq!(Some(Coordinate(9, 0)), input.points().last());
+
+        let folds = input.folds().collect::<Vec<_>>();
+        assert_eq!(2, folds.len());
+        assert_eq!(Some(7), folds[0].get_y());
+        assert_eq!(Some(5), folds[1].get_x());
     }
 
     #[test]
@@ -196,10 +211,18 @@ mod test {
 
     #[test]
     fn example_part_two() {
-        let output = super::part_two(&get_input());
-        assert_eq!(
-            &["#####", "#...#", "#...#", "#...#", "#####", ".....", "....."],
-            &output[..]
-        );
+        let expected = r"
+            #####
+            #...#
+            #...#
+            #...#
+            #####
+            .....
+            .....
+        ";
+        let paper = super::fold_paper(&get_input());
+        assert_eq!(expected.dedent(), super::draw(&paper));
+        // It's a square, not a letter
+        super::part_two(&get_input()).unwrap_err();
     }
 }
@@ -1,14 +1,5 @@
-use advent_of_code_2021::{
-    tools::{MoreItertools, StringTools},
-    util::{self, FixedCollector},
-};
-use std::{
-    collections::{BTreeMap, HashMap},
-    fs, iter,
-    iter::Peekable,
-    path::PathBuf,
-    str::FromStr,
-};
+use advent_of_code_2021::{data::Counter, parsing, tools::MoreItertools, util};
+use std::{collections::HashMap, iter, iter::Peekable, path::PathBuf, str::FromStr};
 
 use itertools::{Either, Itertools};
 use structopt::StructOpt;
@@ -20,37 +11,33 @@ struct Args {
     file: PathBuf,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Rule {
-    pattern: (char, char),
-    insert: char,
-}
+/// The pair of letters that a rule matches against.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Pair(char, char);
 
-impl FromStr for Rule {
+impl FromStr for Pair {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (pattern, extra) = s
-            .split_once(" -> ")
-            .ok_or_else(|| eyre::format_err!("Invalid pattern"))?;
-        let pattern = {
-            let [a, b] = pattern.chars().collect::<FixedCollector<_, 2>>().0?;
-            (a, b)
-        };
-        let [insert] = extra.chars().collect::<FixedCollector<_, 1>>().0?;
-        Ok(Rule { pattern, insert })
+        let [a, b] = s.chars().collect_array()?;
+        Ok(Pair(a, b))
     }
 }
 
+/// Each pair of letters, and the letter that gets inserted between them.
+type Rules = HashMap<Pair, char>;
+
 fn main() -> eyre::Result<()> {
     let args = util::setup::<Args>()?;
-    let file = fs::read_to_string(args.file)?;
-    let mut lines = file.as_str().lines_good();
+    let file = parsing::read_input(args.file)?;
 
-    let template = lines
-        .next()
+    let (template, rules) = file
+        .split_once('\n')
         .ok_or_else(|| eyre::format_err!("Missing start line"))?;
-    let rules = lines.parsed().collect::<Result<Vec<_>, _>>()?;
+    let template = template.trim();
+    // The rules start on the line after the template, which keeps the line numbers in any errors
+    // lined up with the file
+    let rules = parsing::mapping_from_line(rules, " -> ", 2)?;
 
     println!("Part one: {}", part_one(&template, &rules));
     println!("Part two: {}", part_two(&template, &rules));
@@ -58,27 +45,23 @@ fn main() -> eyre::Result<()> {
     Ok(())
 }
 
-struct Foldinator {
-    data: BTreeMap<char, Vec<(char, char)>>,
+struct Foldinator<'a> {
+    rules: &'a Rules,
 }
 
-impl Foldinator {
+impl<'a> Foldinator<'a> {
     fn get_insert(
         &self,
         first: char,
         it: &mut Peekable<impl Iterator<Item = char>>,
     ) -> Option<char> {
-        let second_step = self.data.get(&first)?;
-        let second = it.peek()?;
-        let (_pattern, insert) = second_step
-            .iter()
-            .find(|(pattern, _insert)| second == pattern)?;
-        Some(*insert)
+        let second = *it.peek()?;
+        self.rules.get(&Pair(first, second)).copied()
     }
-    fn fold_polymer<'a>(
-        &'a self,
-        input: impl 'a + Iterator<Item = char>,
-    ) -> impl 'a + Iterator<Item = char> {
+    fn fold_polymer<'b>(
+        &'b self,
+        input: impl 'b + Iterator<Item = char>,
+    ) -> impl 'b + Iterator<Item = char> {
         input
             .peekable()
             .batching(|it| {
@@ -93,17 +76,8 @@ impl Foldinator {
     }
 }
 
-fn part_one(input: &str, rules: &[Rule]) -> u64 {
-    let mut foldinator = Foldinator {
-        data: BTreeMap::new(),
-    };
-    for rule in rules {
-        foldinator
-            .data
-            .entry(rule.pattern.0)
-            .or_insert_with(Vec::new)
-            .push((rule.pattern.1, rule.insert));
-    }
+fn part_one(input: &str, rules: &Rules) -> u128 {
+    let foldinator = Foldinator { rules };
 
     let computed_line = input.chars();
     // Can't be a loop because the type of computed_line changes every time we do this
@@ -119,56 +93,43 @@ fn part_one(input: &str, rules: &[Rule]) -> u64 {
     let computed_line = foldinator.fold_polymer(computed_line); // 9
     let computed_line = foldinator.fold_polymer(computed_line); // 10
 
-    let mut freqs = HashMap::new();
-    for letter in computed_line {
-        *freqs.entry(letter).or_insert(0) += 1;
-    }
-
-    let max = *freqs.values().max().unwrap();
-    let min = *freqs.values().min().unwrap();
-
-    max - min
+    computed_line.collect::<Counter<_>>().spread()
 }
 
-fn part_two(input: &str, rules: &[Rule]) -> u64 {
-    let mut digrams = HashMap::new();
-    for digram in input.chars().tuple_windows() {
-        *digrams.entry(digram).or_insert(0) += 1;
-    }
+fn part_two(input: &str, rules: &Rules) -> u128 {
+    let mut digrams = input.chars().tuple_windows().collect::<Counter<_>>();
 
     for _ in 0..40 {
-        let mut additions = HashMap::new();
-        for rule in rules {
-            if let Some(count) = digrams.remove(&rule.pattern) {
-                *additions.entry((rule.pattern.0, rule.insert)).or_insert(0) += count;
-                *additions.entry((rule.insert, rule.pattern.1)).or_insert(0) += count;
-            }
+        let mut additions = Counter::new();
+        for (&Pair(first, second), &insert) in rules {
+            let count = digrams.remove(&(first, second));
+            additions.add_count((first, insert), count);
+            additions.add_count((insert, second), count);
         }
 
-        for (digram, count) in additions {
-            *digrams.entry(digram).or_insert(0) += count;
-        }
+        digrams += additions;
     }
 
-    let mut doubled_freqs = HashMap::new();
+    // Every letter is in two digrams, apart from the ones on the ends
+    let mut doubled_freqs = Counter::new();
     for ((start, end), count) in digrams {
-        *doubled_freqs.entry(start).or_insert(0) += count;
-        *doubled_freqs.entry(end).or_insert(0) += count;
+        doubled_freqs.add_count(start, count);
+        doubled_freqs.add_count(end, count);
     }
 
-    let max = (doubled_freqs.values().max().unwrap() + 1) / 2;
-    let min = (doubled_freqs.values().min().unwrap() + 1) / 2;
+    let (_, max) = doubled_freqs.max().unwrap();
+    let (_, min) = doubled_freqs.min().unwrap();
 
-    max - min
+    max.div_ceil(2) - min.div_ceil(2)
 }
 
 #[cfg(test)]
 mod test {
-    use super::Rule;
-    use advent_of_code_2021::tools::{MoreItertools, StringTools};
+    use super::Rules;
+    use advent_of_code_2021::parsing;
 
     const EXAMPLE_TEMPLATE: &'static str = "NNCB";
-    fn example_rules() -> Vec<Rule> {
+    fn example_rules() -> Rules {
         let raw = r"
             CH -> B
             HH -> N
@@ -188,10 +149,7 @@ mod test {
             CN -> C
         ";
 
-        raw.lines_good()
-            .parsed()
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap()
+        parsing::mapping(raw, " -> ").unwrap()
     }
 
     #[test]
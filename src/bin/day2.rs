@@ -1,4 +1,8 @@
-use advent_of_code_2021::{parsing, util};
+use advent_of_code_2021::{
+    data::Point,
+    parsing::{self, Keywords},
+    util,
+};
 use std::{path::PathBuf, str::FromStr};
 
 use structopt::StructOpt;
@@ -35,22 +39,12 @@ fn main() -> eyre::Result<()> {
 }
 
 fn part_one(input: &[SubmarineCommand]) -> isize {
-    let h_pos: isize = input
+    let Point(h_pos, v_pos) = input
         .iter()
-        .copied()
-        .filter_map(|input| match input {
-            SubmarineCommand::Forward(x) => Some(x),
-            _ => None,
-        })
-        .sum();
-
-    let v_pos: isize = input
-        .iter()
-        .copied()
-        .filter_map(|input| match input {
-            SubmarineCommand::Down(x) => Some(x),
-            SubmarineCommand::Up(x) => Some(-x),
-            _ => None,
+        .map(|&command| match command {
+            SubmarineCommand::Forward(x) => Point(x, 0),
+            SubmarineCommand::Down(x) => Point(0, x),
+            SubmarineCommand::Up(x) => Point(0, -x),
         })
         .sum();
 
@@ -58,13 +52,12 @@ fn part_one(input: &[SubmarineCommand]) -> isize {
 }
 
 fn part_two(input: &[SubmarineCommand]) -> isize {
-    let (h_pos, v_pos, _aim) = input
+    let (Point(h_pos, v_pos), _aim) = input
         .iter()
-        .cloned()
-        .fold((0, 0, 0), |(h_pos, v_pos, aim), item| match item {
-            SubmarineCommand::Forward(forward) => (h_pos + forward, v_pos + aim * forward, aim),
-            SubmarineCommand::Down(down) => (h_pos, v_pos, aim + down),
-            SubmarineCommand::Up(up) => (h_pos, v_pos, aim - up),
+        .fold((Point(0, 0), 0), |(position, aim), &item| match item {
+            SubmarineCommand::Forward(forward) => (position + Point(1, aim) * forward, aim),
+            SubmarineCommand::Down(down) => (position, aim + down),
+            SubmarineCommand::Up(up) => (position, aim - up),
         });
 
     h_pos * v_pos
@@ -100,15 +93,16 @@ impl FromStr for SubmarineCommand {
         let (direction, magnitude) = s
             .split_once(' ')
             .ok_or_else(|| eyre::format_err!("Invalid command format"))?;
+        const DIRECTIONS: Keywords<fn(isize) -> SubmarineCommand> = Keywords::new(&[
+            ("forward", SubmarineCommand::Forward),
+            ("down", SubmarineCommand::Down),
+            ("up", SubmarineCommand::Up),
+        ]);
+
+        let direction = DIRECTIONS.parse(direction)?;
         let magnitude: isize = magnitude.parse()?;
-        let output = match direction {
-            "forward" => SubmarineCommand::Forward(magnitude),
-            "down" => SubmarineCommand::Down(magnitude),
-            "up" => SubmarineCommand::Up(magnitude),
-            _ => eyre::bail!("Invalid direction"),
-        };
-
-        Ok(output)
+
+        Ok(direction(magnitude))
     }
 }
 
@@ -123,6 +117,8 @@ mod parse_test {
         assert_eq!(SubmarineCommand::Up(10), "up 10".parse().unwrap());
 
         "invalid 1".parse::<SubmarineCommand>().unwrap_err();
+        let error = "foward 1".parse::<SubmarineCommand>().unwrap_err();
+        assert!(error.to_string().contains("did you mean \"forward\"?"));
         "forward      3".parse::<SubmarineCommand>().unwrap_err();
         "".parse::<SubmarineCommand>().unwrap_err();
         "forward ".parse::<SubmarineCommand>().unwrap_err();
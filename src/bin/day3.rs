@@ -1,5 +1,5 @@
-use advent_of_code_2021::{parsing, util};
-use std::{cmp::Ordering, iter, path::PathBuf, str::FromStr};
+use advent_of_code_2021::{data::BitString, parsing, util};
+use std::{cmp::Ordering, path::PathBuf};
 
 use structopt::StructOpt;
 
@@ -10,13 +10,10 @@ struct Args {
     file: PathBuf,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct DiagnosticReport(u32);
-
 fn main() -> eyre::Result<()> {
     let args = util::setup::<Args>()?;
 
-    let lines = parsing::line_separated::<DiagnosticReport, _>(args.file)?;
+    let lines = parsing::line_separated::<BitString, _>(args.file)?;
 
     println!("Part one: {}", part_one(&lines[..])?);
     println!("Part two: {}", part_two(&lines[..])?);
@@ -24,43 +21,11 @@ fn main() -> eyre::Result<()> {
     Ok(())
 }
 
-/// We have a bunch of binary numbers, that we got from strings, but we need to find out how long
-/// they were as strings. Step 1) hope that at least one of them had a 1 in the leading position.
-/// Step 2) find out what the highest set bit is across all the binary numbers.
-fn get_highest_bit(input: &[DiagnosticReport]) -> eyre::Result<u32> {
-    let output = input
-        .iter()
-        .map(|DiagnosticReport(inner)| inner.next_power_of_two() / 2)
-        .max()
-        .ok_or_else(|| eyre::format_err!("The input was empty"))?;
-
-    Ok(output)
-}
-
-/// Walk through a binary number, yielding a bitfield for each bit position in the number. For
-/// example, this will spit out 16, 8, 4, 2, 1 if you start it with 16. By bitwise AND-ing these
-/// masks with a number, you can walk through the bits of the number one at a time, in descending
-/// order of significance.
-fn walk_bits(highest_bit: u32) -> impl Iterator<Item = u32> {
-    iter::successors(Some(highest_bit), move |&x| {
-        let next = x / 2;
-        if next == 0 {
-            None
-        } else {
-            Some(next)
-        }
-    })
-}
-
 /// Find the most common bit value to be at a place within a set of numbers. Returns Some(true) if
 /// more than half of the numbers have a 1 in that place, Some(false) if less than half do, and None
 /// if it's equal.
-fn most_common(mask: u32, set: &[DiagnosticReport]) -> Option<bool> {
-    let matches = set
-        .iter()
-        .copied()
-        .filter(|DiagnosticReport(item)| item & mask != 0)
-        .count();
+fn most_common(column: usize, set: &[BitString]) -> Option<bool> {
+    let matches = set.iter().filter(|item| item[column]).count();
 
     match Ord::cmp(&(matches * 2), &set.len()) {
         Ordering::Less => Some(false),
@@ -69,67 +34,60 @@ fn most_common(mask: u32, set: &[DiagnosticReport]) -> Option<bool> {
     }
 }
 
-fn part_one(input: &[DiagnosticReport]) -> eyre::Result<u32> {
-    let highest_bit = get_highest_bit(input)?;
-
-    let gamma: u32 = walk_bits(highest_bit)
-        .filter(|&bit| most_common(bit, input) == Some(true))
-        .sum();
+fn part_one(input: &[BitString]) -> eyre::Result<u64> {
+    let gamma = BitString::transpose(input)?
+        .iter()
+        .map(|column| column.count_ones() * 2 > column.len())
+        .collect::<BitString>();
 
-    let epsilon = (highest_bit * 2 - 1) ^ gamma;
+    let epsilon = !gamma.clone();
 
-    Ok(gamma * epsilon)
+    Ok(gamma.to_u64()? * epsilon.to_u64()?)
 }
 
-fn part_two(input: &[DiagnosticReport]) -> eyre::Result<u32> {
-    let highest_bit = get_highest_bit(input)?;
+fn part_two(input: &[BitString]) -> eyre::Result<u64> {
+    let width = BitString::width(input)?;
 
     let oxygen = {
         let mut candidates = input.to_vec();
-        for bit in walk_bits(highest_bit) {
+        for column in 0..width {
             if candidates.len() == 1 {
                 break;
             }
-            if most_common(bit, &candidates).unwrap_or(true) {
-                candidates.retain(|DiagnosticReport(item)| item & bit != 0);
-            } else {
-                candidates.retain(|DiagnosticReport(item)| item & bit == 0);
-            }
+            let keep = most_common(column, &candidates).unwrap_or(true);
+            candidates.retain(|item| item[column] == keep);
         }
 
         match &candidates[..] {
-            &[one] => one,
+            [one] => one.to_u64()?,
             _ => eyre::bail!("Too many items left over"),
         }
     };
 
     let co2 = {
         let mut candidates = input.to_vec();
-        for bit in walk_bits(highest_bit) {
+        for column in 0..width {
             if candidates.len() == 1 {
                 break;
             }
-            if !most_common(bit, &candidates).unwrap_or(true) {
-                candidates.retain(|DiagnosticReport(item)| item & bit != 0)
-            } else {
-                candidates.retain(|DiagnosticReport(item)| item & bit == 0)
-            }
+            let keep = !most_common(column, &candidates).unwrap_or(true);
+            candidates.retain(|item| item[column] == keep);
         }
 
         match &candidates[..] {
-            &[one] => one,
+            [one] => one.to_u64()?,
             _ => eyre::bail!("Too many items left over"),
         }
     };
 
-    Ok(oxygen.0 * co2.0)
+    Ok(oxygen * co2)
 }
 
 #[cfg(test)]
 mod test {
-    use super::DiagnosticReport;
+    use advent_of_code_2021::data::BitString;
 
-    fn get_example_input() -> eyre::Result<Vec<DiagnosticReport>> {
+    fn get_example_input() -> eyre::Result<Vec<BitString>> {
         let output = vec![
             "00100".parse()?,
             "11110".parse()?,
@@ -158,13 +116,15 @@ mod test {
         assert_eq!(230, super::part_two(&get_example_input()?[..])?);
         Ok(())
     }
-}
-
-impl FromStr for DiagnosticReport {
-    type Err = eyre::Report;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let output = u32::from_str_radix(s, 2)?;
-        Ok(DiagnosticReport(output))
+    #[test]
+    fn keeps_leading_zero_columns() -> eyre::Result<()> {
+        // No line starts with a 1, but that column still counts towards epsilon
+        let input = get_example_input()?
+            .into_iter()
+            .map(|line| format!("0{}", line).parse())
+            .collect::<eyre::Result<Vec<BitString>>>()?;
+        assert_eq!(22 * (32 + 9), super::part_one(&input)?);
+        Ok(())
     }
 }
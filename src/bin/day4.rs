@@ -1,8 +1,5 @@
-use advent_of_code_2021::{
-    tools::MoreItertools,
-    util::{self, FixedCollector},
-};
-use std::{fs, path::PathBuf, str::FromStr};
+use advent_of_code_2021::{parsing, tools::MoreItertools, util};
+use std::{path::PathBuf, str::FromStr};
 
 use itertools::Itertools;
 use structopt::StructOpt;
@@ -28,15 +25,11 @@ impl FromStr for BingoBoard {
         let numbers = s
             .lines()
             .map(|line| {
-                let row = line
-                    .split_whitespace()
+                line.split_whitespace()
                     .map(|num| num.trim().parse::<u32>())
-                    .collect::<Result<FixedCollector<_, 5>, _>>()?
-                    .0?;
-                Ok::<_, eyre::Report>(row)
+                    .try_collect_array()
             })
-            .collect::<Result<FixedCollector<_, 5>, _>>()?
-            .0?;
+            .try_collect_array()?;
 
         Ok(BingoBoard { complete, numbers })
     }
@@ -93,7 +86,7 @@ fn parse_bingo(input: &str) -> eyre::Result<(Vec<u32>, Vec<BingoBoard>)> {
 
 fn main() -> eyre::Result<()> {
     let args = util::setup::<Args>()?;
-    let input = fs::read_to_string(args.file)?;
+    let input = parsing::read_input(args.file)?;
 
     let (bingo_numbers, boards) = parse_bingo(&input)?;
 
@@ -155,6 +148,8 @@ fn part_two(
 
 #[cfg(test)]
 mod test {
+    use advent_of_code_2021::tools::StringTools;
+
     const INPUT_FILE: &'static str = r"
         7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
@@ -177,9 +172,16 @@ mod test {
          2  0 12  3  7
     ";
 
+    #[test]
+    fn example_keeps_alignment() {
+        let input = INPUT_FILE.dedent();
+        assert_eq!(Some(" 8  2 23  4 24"), input.lines().nth(3));
+        assert_eq!(Some(""), input.lines().nth(7));
+    }
+
     #[test]
     fn parses_correctly() {
-        let (numbers, boards) = super::parse_bingo(INPUT_FILE).unwrap();
+        let (numbers, boards) = super::parse_bingo(&INPUT_FILE.dedent()).unwrap();
         assert_eq!(27, numbers.len());
         assert_eq!(7, numbers[0]);
 
@@ -198,13 +200,13 @@ mod test {
 
     #[test]
     fn example_part_one() {
-        let (numbers, boards) = super::parse_bingo(INPUT_FILE).unwrap();
+        let (numbers, boards) = super::parse_bingo(&INPUT_FILE.dedent()).unwrap();
         assert_eq!(4512, super::part_one(numbers.into_iter(), boards).unwrap());
     }
 
     #[test]
     fn example_part_two() {
-        let (numbers, boards) = super::parse_bingo(INPUT_FILE).unwrap();
+        let (numbers, boards) = super::parse_bingo(&INPUT_FILE.dedent()).unwrap();
         assert_eq!(1924, super::part_two(numbers.into_iter(), boards).unwrap());
     }
 }
@@ -1,5 +1,15 @@
-use advent_of_code_2021::{data::Coordinate, parsing, tools::StringTools, util};
-use std::{cmp, collections::HashSet, path::PathBuf, str::FromStr};
+use advent_of_code_2021::{
+    data::{Grid, Segment},
+    parsing,
+    render::{self, Gradient},
+    util,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::BufWriter,
+    path::PathBuf,
+};
 
 use structopt::StructOpt;
 
@@ -8,75 +18,48 @@ struct Args {
     #[structopt(default_value = "./data/day-five.txt")]
     /// The path to the input file we want to run with.
     file: PathBuf,
+    #[structopt(long)]
+    /// Also draw how many vents cover each point as a PPM image.
+    image: Option<PathBuf>,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Line {
-    start: Coordinate,
-    end: Coordinate,
-}
+fn main() -> eyre::Result<()> {
+    let args = util::setup::<Args>()?;
 
-impl FromStr for Line {
-    type Err = eyre::Report;
+    let lines = parsing::line_separated::<Segment, _>(args.file)?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_parse(" -> ")?;
+    println!("Part one: {}", part_one(lines.iter().copied()));
+    println!("Part two: {}", part_two(lines.iter().copied()));
 
-        let output = Line { start, end };
-        Ok(output)
+    if let Some(path) = args.image {
+        let vents = vent_map(lines.iter().copied());
+        let most = vents.values().copied().max().unwrap_or(0);
+        let file = BufWriter::new(File::create(path)?);
+        render::write_ppm(
+            &vents,
+            |&count| Gradient::HEAT.scaled(count as f64, 0.0, most as f64),
+            file,
+        )?;
     }
+    Ok(())
 }
 
-impl Line {
-    fn is_non_diagonal(&self) -> bool {
-        self.start.0 == self.end.0 || self.start.1 == self.end.1
+/// Count how many lines cover each point, across the part of the sea floor that has any vents.
+fn vent_map(input: impl Iterator<Item = Segment>) -> Grid<usize> {
+    let mut counts = HashMap::new();
+    for point in input.flat_map(Segment::points) {
+        *counts.entry(point).or_insert(0) += 1;
     }
 
-    fn points(self) -> impl Iterator<Item = Coordinate> {
-        let min = cmp::min(self.start, self.end);
-        let max = cmp::max(self.start, self.end);
-
-        let range = if self.start.0 == self.end.0 {
-            (min.1)..=(max.1)
-        } else if self.start.1 == self.end.1 {
-            (min.0)..=(max.0)
-        } else {
-            (min.0)..=(max.0)
-        };
-
-        range.map(move |item| {
-            if self.start.0 == self.end.0 {
-                Coordinate(self.start.0, item)
-            } else if self.start.1 == self.end.1 {
-                Coordinate(item, self.start.1)
-            } else {
-                let delta = item - min.0;
-                if max.1 > min.1 {
-                    Coordinate(item, min.1 + delta)
-                } else {
-                    Coordinate(item, min.1 - delta)
-                }
-            }
-        })
-    }
-}
-
-fn main() -> eyre::Result<()> {
-    let args = util::setup::<Args>()?;
-
-    let lines = parsing::line_separated::<Line, _>(args.file)?;
-
-    println!("Part one: {}", part_one(lines.iter().copied()));
-    println!("Part two: {}", part_two(lines.iter().copied()));
-    Ok(())
+    render::plot_values(&counts, 0)
 }
 
-fn count_overlaps(input: impl Iterator<Item = Line>) -> usize {
+fn count_overlaps(input: impl Iterator<Item = Segment>) -> usize {
     let mut seen = HashSet::new();
     let mut seen_twice = HashSet::new();
 
     input
-        .flat_map(|line| line.points())
+        .flat_map(Segment::points)
         .filter(|&point| {
             if seen.contains(&point) {
                 // Returns true if the point **is not** in seen_twice yet, meaning this is the
@@ -90,39 +73,49 @@ fn count_overlaps(input: impl Iterator<Item = Line>) -> usize {
         .count()
 }
 
-fn part_one(input: impl Iterator<Item = Line>) -> usize {
-    count_overlaps(input.filter(|line| line.is_non_diagonal()))
+fn part_one(input: impl Iterator<Item = Segment>) -> usize {
+    count_overlaps(input.filter(|line| line.is_axis_aligned()))
 }
 
-fn part_two(input: impl Iterator<Item = Line>) -> usize {
+fn part_two(input: impl Iterator<Item = Segment>) -> usize {
     count_overlaps(input)
 }
 
 #[cfg(test)]
 mod test {
-    use super::Line;
-    use advent_of_code_2021::data::Coordinate;
-
-    const EXAMPLE_INPUT: &'static [Line] = &[
-        Line { start: Coordinate(0, 9), end: Coordinate(5, 9) },
-        Line { start: Coordinate(8, 0), end: Coordinate(0, 8) },
-        Line { start: Coordinate(9, 4), end: Coordinate(3, 4) },
-        Line { start: Coordinate(2, 2), end: Coordinate(2, 1) },
-        Line { start: Coordinate(7, 0), end: Coordinate(7, 4) },
-        Line { start: Coordinate(6, 4), end: Coordinate(2, 0) },
-        Line { start: Coordinate(0, 9), end: Coordinate(2, 9) },
-        Line { start: Coordinate(3, 4), end: Coordinate(1, 4) },
-        Line { start: Coordinate(0, 0), end: Coordinate(8, 8) },
-        Line { start: Coordinate(5, 5), end: Coordinate(8, 2) },
+    use advent_of_code_2021::data::Segment;
+
+    const EXAMPLE_INPUT: &[&str] = &[
+        "0,9 -> 5,9",
+        "8,0 -> 0,8",
+        "9,4 -> 3,4",
+        "2,2 -> 2,1",
+        "7,0 -> 7,4",
+        "6,4 -> 2,0",
+        "0,9 -> 2,9",
+        "3,4 -> 1,4",
+        "0,0 -> 8,8",
+        "5,5 -> 8,2",
     ];
 
+    fn get_input() -> impl Iterator<Item = Segment> {
+        EXAMPLE_INPUT.iter().map(|line| line.parse().unwrap())
+    }
+
     #[test]
     fn example_part_one() {
-        assert_eq!(5, super::part_one(EXAMPLE_INPUT.iter().copied()))
+        assert_eq!(5, super::part_one(get_input()))
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(12, super::part_two(EXAMPLE_INPUT.iter().copied()))
+        assert_eq!(12, super::part_two(get_input()))
+    }
+
+    #[test]
+    fn maps_negative_vents() {
+        let lines = ["-3,-1 -> -1,-1", "-2,-2 -> -2,0"].map(|line| line.parse().unwrap());
+        let vents = super::vent_map(lines.into_iter());
+        assert_eq!("010\n121\n010\n", vents.to_string());
     }
 }
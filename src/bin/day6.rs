@@ -1,5 +1,5 @@
-use advent_of_code_2021::{parsing, util};
-use std::{collections::VecDeque, path::PathBuf};
+use advent_of_code_2021::{data::Counter, parsing, util};
+use std::path::PathBuf;
 
 use structopt::StructOpt;
 
@@ -20,22 +20,27 @@ fn main() -> eyre::Result<()> {
     Ok(())
 }
 
-fn let_them_grow(input: &[usize], days: u32) -> u64 {
-    let mut state = VecDeque::from([0u64; 9]);
-    input.iter().copied().for_each(|age| {
-        state[age] += 1;
-    });
+fn let_them_grow(input: &[usize], days: u32) -> u128 {
+    // how many fish there are with each number of days left until they give birth
+    let mut state = input.iter().copied().collect::<Counter<usize>>();
 
     for _ in 0..days {
-        // bring every bucket one day closer to the front, and also find the fish that are ready
-        let about_to_pop = state.pop_front().unwrap();
-        // reset the parents
-        state[6] += about_to_pop;
-        // add the children to the back of the queue (which is always the "8 days to go" bucket)
-        state.push_back(about_to_pop)
+        let mut next = Counter::new();
+        for (age, count) in state {
+            match age {
+                // reset the parents, and add the children with 8 days to go
+                0 => {
+                    next.add_count(6, count);
+                    next.add_count(8, count);
+                }
+                // everyone else is one day closer
+                age => next.add_count(age - 1, count),
+            }
+        }
+        state = next;
     }
 
-    state.into_iter().sum()
+    state.total()
 }
 
 #[cfg(test)]
@@ -1,7 +1,4 @@
-use advent_of_code_2021::{
-    parsing,
-    util::{self, FixedCollector},
-};
+use advent_of_code_2021::{data::BitSet, parsing, tools::MoreItertools, util};
 use std::{path::PathBuf, str::FromStr};
 
 use structopt::StructOpt;
@@ -13,38 +10,25 @@ struct Args {
     file: PathBuf,
 }
 
+/// The segments that are lit up on a display, where segment `a` is 0, `b` is 1, and so on.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct SevenSeg(u8);
+struct SevenSeg(BitSet<7>);
 
 impl FromStr for SevenSeg {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = s.chars().try_fold(0, |sum, ch| {
-            let bit = match ch {
-                'a' => 1,
-                'b' => 2,
-                'c' => 3,
-                'd' => 4,
-                'e' => 5,
-                'f' => 6,
-                'g' => 7,
-                other => eyre::bail!("Invalid character {}", other),
-            };
-            Ok(sum + (1 << bit))
-        })?;
-
-        Ok(SevenSeg(value))
+        Ok(SevenSeg(BitSet::from_alphabet(s, "abcdefg")?))
     }
 }
 
 impl SevenSeg {
-    fn len(self) -> u32 {
-        self.0.count_ones()
+    fn len(self) -> usize {
+        self.0.len()
     }
 
     fn contains(self, other: Self) -> bool {
-        other.0 & self.0 == other.0
+        self.0.is_superset(other.0)
     }
 }
 
@@ -61,16 +45,8 @@ impl FromStr for Input {
         let (first, second) = s
             .split_once(" | ")
             .ok_or_else(|| eyre::format_err!("Invalid format"))?;
-        let digits = first
-            .split(" ")
-            .map(|seg| seg.parse())
-            .collect::<Result<FixedCollector<_, 10>, _>>()?
-            .0?;
-        let display_value = second
-            .split(" ")
-            .map(|seg| seg.parse())
-            .collect::<Result<FixedCollector<_, 4>, _>>()?
-            .0?;
+        let digits = first.split(' ').parsed().try_collect_array()?;
+        let display_value = second.split(' ').parsed().try_collect_array()?;
         Ok(Input {
             digits,
             display_value,
@@ -98,14 +74,13 @@ fn part_one(input: &[Input]) -> usize {
         .count()
 }
 
-fn find_with_len<const N: usize>(len: u32, input: &Input) -> eyre::Result<[SevenSeg; N]> {
+fn find_with_len<const N: usize>(len: usize, input: &Input) -> eyre::Result<[SevenSeg; N]> {
     input
         .digits
         .iter()
         .copied()
         .filter(|&x| x.len() == len)
-        .collect::<FixedCollector<_, N>>()
-        .0
+        .collect_array()
 }
 
 fn part_two(input: &[Input]) -> eyre::Result<usize> {
@@ -143,10 +118,13 @@ fn part_two(input: &[Input]) -> eyre::Result<usize> {
             let [a, b, c, d] = input
                 .display_value
                 .into_iter()
-                .map(|this_digit| digits.iter().position(|&other| this_digit == other))
-                .collect::<Option<FixedCollector<_, 4>>>()
-                .ok_or_else(|| eyre::format_err!("Invalid digit found"))?
-                .0?;
+                .map(|this_digit| {
+                    digits
+                        .iter()
+                        .position(|&other| this_digit == other)
+                        .ok_or_else(|| eyre::format_err!("Invalid digit found"))
+                })
+                .try_collect_array()?;
 
             Ok(a * 1000 + b * 100 + c * 10 + d)
         })
@@ -1,4 +1,4 @@
-use advent_of_code_2021::{parsing, util::{self, FixedCollector}};
+use advent_of_code_2021::{parsing, util};
 use std::{path::PathBuf, str::FromStr};
 
 use structopt::StructOpt;
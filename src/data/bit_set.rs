@@ -0,0 +1,306 @@
+use std::{
+    fmt,
+    iter::FromIterator,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign},
+};
+
+/// A set of small numbers, from `0` up to (but not including) `N`, packed into the bits of a single
+/// integer. It's `Copy` and never allocates, so it's good for things like the segments of a
+/// display or the letters in a short word. `N` can be at most 128.
+#[derive(Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct BitSet<const N: usize> {
+    bits: u128,
+}
+
+impl<const N: usize> BitSet<N> {
+    /// The set with nothing in it.
+    pub const EMPTY: BitSet<N> = BitSet { bits: 0 };
+
+    /// The bits that are allowed to be set.
+    const MASK: u128 = {
+        assert!(N <= 128, "A BitSet can only hold up to 128 bits");
+        if N == 128 {
+            u128::MAX
+        } else {
+            (1 << N) - 1
+        }
+    };
+
+    pub fn new() -> Self {
+        BitSet::EMPTY
+    }
+
+    /// The set with every number from `0` to `N` in it.
+    pub fn full() -> Self {
+        BitSet { bits: Self::MASK }
+    }
+
+    /// Make a set from an integer, where bit `i` being set means `i` is in the set. Any bits past
+    /// the end of the set are ignored.
+    pub fn from_bits(bits: u128) -> Self {
+        BitSet {
+            bits: bits & Self::MASK,
+        }
+    }
+
+    /// The set as an integer, where bit `i` is set if `i` is in the set.
+    pub fn bits(self) -> u128 {
+        self.bits
+    }
+
+    /// Parse a set of characters, where each character in `alphabet` stands for its position. So
+    /// with an alphabet of `"abcdefg"`, `"bad"` would be the set `{0, 1, 3}`.
+    pub fn from_alphabet(s: &str, alphabet: &str) -> eyre::Result<Self> {
+        s.chars()
+            .map(|ch| {
+                alphabet
+                    .chars()
+                    .position(|other| other == ch)
+                    .filter(|&idx| idx < N)
+                    .ok_or_else(|| {
+                        eyre::format_err!(
+                            "Invalid character {:?}, expected one of {:?}",
+                            ch,
+                            alphabet
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Parse a string of `0`s and `1`s, written like a binary number, so the last character is
+    /// whether `0` is in the set.
+    pub fn from_binary(s: &str) -> eyre::Result<Self> {
+        eyre::ensure!(
+            s.len() <= N,
+            "{:?} has {} digits, but this set only has room for {}",
+            s,
+            s.len(),
+            N
+        );
+        s.chars()
+            .rev()
+            .enumerate()
+            .try_fold(BitSet::EMPTY, |mut set, (idx, ch)| {
+                match ch {
+                    '0' => {}
+                    '1' => {
+                        set.insert(idx);
+                    }
+                    other => eyre::bail!("Invalid binary digit {:?}", other),
+                }
+                Ok(set)
+            })
+    }
+
+    /// The largest number of items that can be in the set.
+    pub const fn capacity(self) -> usize {
+        N
+    }
+
+    /// How many numbers are in the set.
+    pub fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn contains(self, value: usize) -> bool {
+        value < N && self.bits & (1 << value) != 0
+    }
+
+    /// Add a number to the set, returning whether it's new. Panics if the number doesn't fit.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(value < N, "{} doesn't fit in a BitSet<{}>", value, N);
+        let new = !self.contains(value);
+        self.bits |= 1 << value;
+        new
+    }
+
+    /// Take a number out of the set, returning whether it was there in the first place.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let found = self.contains(value);
+        if found {
+            self.bits &= !(1 << value);
+        }
+        found
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        BitSet {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        BitSet {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    /// Everything in this set that isn't in the other one.
+    pub fn difference(self, other: Self) -> Self {
+        BitSet {
+            bits: self.bits & !other.bits,
+        }
+    }
+
+    /// Everything that's in one set or the other, but not both.
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        BitSet {
+            bits: self.bits ^ other.bits,
+        }
+    }
+
+    /// Everything from `0` to `N` that isn't in this set.
+    pub fn complement(self) -> Self {
+        BitSet {
+            bits: !self.bits & Self::MASK,
+        }
+    }
+
+    /// Whether everything in this set is also in the other one.
+    pub fn is_subset(self, other: Self) -> bool {
+        self.bits & other.bits == self.bits
+    }
+
+    /// Whether everything in the other set is also in this one.
+    pub fn is_superset(self, other: Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(self, other: Self) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    /// Iterate over the numbers in the set, from smallest to largest.
+    pub fn iter(self) -> Bits {
+        Bits { bits: self.bits }
+    }
+}
+
+/// See `BitSet::iter`.
+#[derive(Debug, Clone)]
+pub struct Bits {
+    bits: u128,
+}
+
+impl Iterator for Bits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let value = self.bits.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.bits &= self.bits - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Bits {}
+
+impl<const N: usize> IntoIterator for BitSet<N> {
+    type Item = usize;
+    type IntoIter = Bits;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Collect numbers into a set. Panics if any of them don't fit.
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut output = BitSet::EMPTY;
+        for value in iter {
+            output.insert(value);
+        }
+        output
+    }
+}
+
+/// Print the set like any other set, as `{1, 3, 5}`.
+impl<const N: usize> fmt::Debug for BitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Print the set as a binary number, with all `N` digits.
+impl<const N: usize> fmt::Display for BitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0width$b}", self.bits, width = N)
+    }
+}
+
+macro_rules! impl_set_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $set_method:ident) => {
+        impl<const N: usize> $trait for BitSet<N> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self::Output {
+                self.$set_method(other)
+            }
+        }
+
+        impl<const N: usize> $assign_trait for BitSet<N> {
+            fn $assign_method(&mut self, other: Self) {
+                *self = self.$set_method(other);
+            }
+        }
+    };
+}
+
+impl_set_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+impl_set_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+impl_set_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference
+);
+impl_set_operator!(Sub, sub, SubAssign, sub_assign, difference);
+
+impl<const N: usize> Not for BitSet<N> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BitSet;
+
+    #[test]
+    fn does_set_operations() {
+        let a = BitSet::<7>::from_alphabet("bad", "abcdefg").unwrap();
+        let b = [1, 4].into_iter().collect::<BitSet<7>>();
+        assert_eq!(vec![0, 1, 3], a.iter().collect::<Vec<_>>());
+        assert_eq!(3, a.len());
+        assert_eq!("{0, 1, 3, 4}", format!("{:?}", a | b));
+        assert_eq!("0000010", (a & b).to_string());
+        assert_eq!(BitSet::from_bits(0b1001), a - b);
+        assert_eq!(BitSet::from_binary("1110100").unwrap(), !a);
+        assert!((a & b).is_subset(b));
+        assert!(a.is_superset(a & b));
+        assert!(a.is_disjoint(!a));
+        assert_eq!(7, (a | !a).len());
+
+        BitSet::<7>::from_alphabet("abz", "abcdefg").unwrap_err();
+        BitSet::<7>::from_alphabet("h", "abcdefgh").unwrap_err();
+        BitSet::<3>::from_binary("1000").unwrap_err();
+        assert_eq!(128, BitSet::<128>::full().len());
+    }
+}
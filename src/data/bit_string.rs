@@ -0,0 +1,186 @@
+use std::{
+    fmt,
+    iter::FromIterator,
+    ops::{Index, Not, Range},
+    str::FromStr,
+};
+
+/// A string of bits, parsed from binary or hexadecimal text. Unlike parsing straight into an
+/// integer, this keeps track of exactly how wide the input was (leading zeros and all), and it can
+/// be as wide as you like. Bits are indexed from the left, so index 0 is the most significant bit.
+#[derive(Debug, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct BitString {
+    bits: Vec<bool>,
+}
+
+impl BitString {
+    /// Parse a string of `0`s and `1`s.
+    pub fn from_binary(s: &str) -> eyre::Result<Self> {
+        s.chars()
+            .map(|ch| match ch {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                other => eyre::bail!("Invalid binary digit {:?}", other),
+            })
+            .collect()
+    }
+
+    /// Parse a string of hexadecimal digits, with each digit becoming four bits.
+    pub fn from_hex(s: &str) -> eyre::Result<Self> {
+        let mut bits = Vec::with_capacity(s.len() * 4);
+        for ch in s.chars() {
+            let digit = ch
+                .to_digit(16)
+                .ok_or_else(|| eyre::format_err!("Invalid hex digit {:?}", ch))?;
+            bits.extend((0..4).rev().map(|shift| digit & (1 << shift) != 0));
+        }
+
+        Ok(BitString { bits })
+    }
+
+    /// The number of bits, including any leading zeros.
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Get a bit, counting from the left.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.bits.get(index).copied()
+    }
+
+    /// Iterate over the bits, from the most significant to the least.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = bool> {
+        self.bits.iter().copied()
+    }
+
+    /// The number of bits that are set.
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().filter(|&&bit| bit).count()
+    }
+
+    /// Copy out a range of bits, as their own bit string. Handy for reading fields out of a packet.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        BitString {
+            bits: self.bits[range].to_vec(),
+        }
+    }
+
+    /// Read the bits as an unsigned number. Fails if there are more than 64 bits, even if the
+    /// leading ones are all zeros - slice them off first if that's what you want.
+    pub fn to_u64(&self) -> eyre::Result<u64> {
+        eyre::ensure!(
+            self.len() <= 64,
+            "{} bits is too wide to fit in a u64",
+            self.len()
+        );
+        Ok(self.iter().fold(0, |acc, bit| (acc << 1) | bit as u64))
+    }
+
+    /// Turn a set of rows of bits into a set of columns, so that column `i` holds the `i`th bit of
+    /// every row. All of the rows must be the same width.
+    pub fn transpose(rows: &[Self]) -> eyre::Result<Vec<Self>> {
+        let width = Self::width(rows)?;
+        let output = (0..width)
+            .map(|column| rows.iter().map(|row| row[column]).collect())
+            .collect();
+
+        Ok(output)
+    }
+
+    /// Find the width shared by a set of rows of bits, failing if there are no rows or if they
+    /// aren't all the same width.
+    pub fn width(rows: &[Self]) -> eyre::Result<usize> {
+        let width = rows
+            .first()
+            .ok_or_else(|| eyre::format_err!("No rows of bits found"))?
+            .len();
+        eyre::ensure!(
+            rows.iter().all(|row| row.len() == width),
+            "All rows of bits must be the same width"
+        );
+
+        Ok(width)
+    }
+}
+
+impl FromStr for BitString {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BitString::from_binary(s)
+    }
+}
+
+impl FromIterator<bool> for BitString {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        BitString {
+            bits: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Index<usize> for BitString {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.bits.index(index)
+    }
+}
+
+impl Not for BitString {
+    type Output = BitString;
+
+    fn not(self) -> Self::Output {
+        self.iter().map(|bit| !bit).collect()
+    }
+}
+
+impl fmt::Display for BitString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BitString;
+
+    #[test]
+    fn keeps_leading_zeros() {
+        let bits = "00101".parse::<BitString>().unwrap();
+        assert_eq!(5, bits.len());
+        assert_eq!(5, bits.to_u64().unwrap());
+        assert_eq!("11010", (!bits).to_string());
+
+        let bits = BitString::from_hex("0A").unwrap();
+        assert_eq!("00001010", bits.to_string());
+        BitString::from_hex("0G").unwrap_err();
+    }
+
+    #[test]
+    fn handles_wide_inputs() {
+        let bits = BitString::from_hex("D2FE28D2FE28D2FE28").unwrap();
+        assert_eq!(72, bits.len());
+        bits.to_u64().unwrap_err();
+        assert_eq!(6, bits.slice(0..3).to_u64().unwrap());
+        assert_eq!(4, bits.slice(3..6).to_u64().unwrap());
+    }
+
+    #[test]
+    fn transposes_columns() {
+        let rows = ["001", "011"].map(|row| row.parse::<BitString>().unwrap());
+        let columns = BitString::transpose(&rows).unwrap();
+        let columns = columns.iter().map(|col| col.to_string()).collect::<Vec<_>>();
+        assert_eq!(["00", "01", "11"], &columns[..]);
+
+        let ragged = ["001", "01"].map(|row| row.parse::<BitString>().unwrap());
+        BitString::transpose(&ragged).unwrap_err();
+    }
+}
@@ -1,3 +1,4 @@
+use super::{Boundary, Neighbours, Offsets, Point};
 use crate::tools::StringTools;
 use std::str::FromStr;
 
@@ -22,55 +23,40 @@ impl Coordinate {
         (0..max_x).flat_map(move |x| (0..max_y).map(move |y| Coordinate(x, y)))
     }
 
-    /// Find all of the manhattan neighbours of this point (IE no diagonals) that lie within the
-    /// grid defined by the corner points `(0,0)` and `max_point`.
-    pub fn manhattan_neighbours(self, max_point: Self) -> impl Iterator<Item = Self> {
+    /// Move this coordinate by an offset, as long as that doesn't take it below zero.
+    pub fn offset(self, Point(dx, dy): Point) -> Option<Self> {
         let Coordinate(x, y) = self;
-        let Coordinate(max_x, max_y) = max_point;
+        Some(Coordinate(x.checked_add_signed(dx)?, y.checked_add_signed(dy)?))
+    }
 
-        let mut output = Vec::new();
-        if x > 0 {
-            output.push(Coordinate(x - 1, y));
-        }
-        if x + 1 < max_x {
-            output.push(Coordinate(x + 1, y));
-        }
-        if y > 0 {
-            output.push(Coordinate(x, y - 1));
-        }
-        if y + 1 < max_y {
-            output.push(Coordinate(x, y + 1));
-        }
+    /// Move this coordinate by an offset within the grid defined by the corner points `(0,0)` and
+    /// `max_point`, where the boundary decides what happens at the edges of the grid.
+    pub fn step(self, delta: Point, max_point: Self, boundary: Boundary) -> Option<Self> {
+        boundary.step(self, delta, max_point)
+    }
 
-        output.into_iter()
+    /// Find all of the neighbours of this point within the grid defined by the corner points
+    /// `(0,0)` and `max_point`, where the offsets decide what counts as a neighbour and the boundary
+    /// decides what happens at the edges of the grid. This doesn't allocate, so it's fine to call
+    /// in a hot loop.
+    pub fn neighbours<'a>(
+        self,
+        max_point: Self,
+        offsets: Offsets<'a>,
+        boundary: Boundary,
+    ) -> Neighbours<'a> {
+        Neighbours::new(self, max_point, offsets, boundary)
+    }
+
+    /// Find all of the manhattan neighbours of this point (IE no diagonals) that lie within the
+    /// grid defined by the corner points `(0,0)` and `max_point`.
+    pub fn manhattan_neighbours(self, max_point: Self) -> Neighbours<'static> {
+        self.neighbours(max_point, Offsets::MANHATTAN, Boundary::Clip)
     }
 
     /// Find all of the neighbours of this point (including diagonals) that lie within the grid
     /// defined by the corner points `(0,0)` and `max_point`.
-    pub fn all_neighbours(self, max_point: Self) -> impl Iterator<Item = Self> {
-        let Coordinate(x, y) = self;
-        let Coordinate(max_x, max_y) = max_point;
-
-        let mut output = Vec::new();
-
-        for dx in -1..=1 {
-            if x == 0 && dx == -1 || (x + 1 == max_x && dx == 1) {
-                continue;
-            }
-
-            for dy in -1..=1 {
-                if dx == 0 && dy == 0 {
-                    continue;
-                }
-
-                if y == 0 && dy == -1 || (y + 1 == max_y && dy == 1) {
-                    continue;
-                }
-
-                output.push(Coordinate((x as isize + dx) as usize, (y as isize + dy) as usize))
-            }
-        }
-
-        output.into_iter()
+    pub fn all_neighbours(self, max_point: Self) -> Neighbours<'static> {
+        self.neighbours(max_point, Offsets::ALL, Boundary::Clip)
     }
 }
@@ -0,0 +1,232 @@
+use std::{
+    collections::{hash_map, HashMap},
+    hash::Hash,
+    iter::FromIterator,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// Counts how many times each thing has been seen, like a bag of items. Counts are `u128`, since
+/// the puzzles that need counting tend to be the ones where the numbers grow exponentially.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, u128>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Start off with pairs of items and how many of them there are. This isn't a `FromIterator`
+    /// impl, since that would clash with counting items that are pairs themselves.
+    pub fn from_counts(counts: impl IntoIterator<Item = (T, u128)>) -> Self {
+        let mut output = Counter::new();
+        for (item, count) in counts {
+            output.add_count(item, count);
+        }
+        output
+    }
+
+    /// Count one more of an item.
+    pub fn add(&mut self, item: T) {
+        self.add_count(item, 1);
+    }
+
+    /// Count lots more of an item at once.
+    pub fn add_count(&mut self, item: T, count: u128) {
+        if count != 0 {
+            *self.counts.entry(item).or_insert(0) += count;
+        }
+    }
+
+    /// Take away some of an item, stopping at zero. Items with nothing left are forgotten about.
+    pub fn subtract(&mut self, item: T, count: u128) {
+        if let hash_map::Entry::Occupied(mut entry) = self.counts.entry(item) {
+            *entry.get_mut() = entry.get().saturating_sub(count);
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
+    /// Forget about an item entirely, returning how many of it there were.
+    pub fn remove(&mut self, item: &T) -> u128 {
+        self.counts.remove(item).unwrap_or(0)
+    }
+
+    /// How many of an item there are, which is zero for anything that hasn't been seen.
+    pub fn get(&self, item: &T) -> u128 {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Add all of the counts from another counter into this one.
+    pub fn merge(&mut self, other: Counter<T>) {
+        for (item, count) in other.counts {
+            self.add_count(item, count);
+        }
+    }
+
+    /// Multiply every count by the same amount, like when every item turns into `factor` copies
+    /// of itself.
+    pub fn scale(&mut self, factor: u128) {
+        if factor == 0 {
+            self.counts.clear();
+        }
+        for count in self.counts.values_mut() {
+            *count *= factor;
+        }
+    }
+
+    /// The number of different items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The number of items, counting every copy.
+    pub fn total(&self) -> u128 {
+        self.counts.values().sum()
+    }
+
+    /// Iterate over each item and how many of it there are, in no particular order.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (&T, u128)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    /// The `k` items with the highest counts, from most to least common. Ties come out in no
+    /// particular order.
+    pub fn most_common(&self, k: usize) -> Vec<(&T, u128)> {
+        let mut output = self.iter().collect::<Vec<_>>();
+        output.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
+        output.truncate(k);
+        output
+    }
+
+    /// The `k` items with the lowest counts, from least to most common. Ties come out in no
+    /// particular order.
+    pub fn least_common(&self, k: usize) -> Vec<(&T, u128)> {
+        let mut output = self.iter().collect::<Vec<_>>();
+        output.sort_unstable_by_key(|&(_, count)| count);
+        output.truncate(k);
+        output
+    }
+
+    /// The most common item, and how many of it there are.
+    pub fn max(&self) -> Option<(&T, u128)> {
+        self.iter().max_by_key(|&(_, count)| count)
+    }
+
+    /// The least common item, and how many of it there are.
+    pub fn min(&self) -> Option<(&T, u128)> {
+        self.iter().min_by_key(|&(_, count)| count)
+    }
+
+    /// The difference between the counts of the most and least common items.
+    pub fn spread(&self) -> u128 {
+        match (self.max(), self.min()) {
+            (Some((_, max)), Some((_, min))) => max - min,
+            _ => 0,
+        }
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut output = Counter::new();
+        output.extend(iter);
+        output
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, u128);
+    type IntoIter = hash_map::IntoIter<T, u128>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self.merge(other);
+        self
+    }
+}
+
+impl<T: Eq + Hash> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.merge(other);
+    }
+}
+
+/// Take away the counts in another counter, stopping at zero.
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self::Output {
+        self -= other;
+        self
+    }
+}
+
+impl<T: Eq + Hash> SubAssign for Counter<T> {
+    fn sub_assign(&mut self, other: Self) {
+        for (item, count) in other.counts {
+            self.subtract(item, count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Counter;
+
+    #[test]
+    fn counts_things() {
+        let mut letters = "NBBBCNCCNBBNBNBBCHBHHBCHB".chars().collect::<Counter<_>>();
+        assert_eq!(11, letters.get(&'B'));
+        assert_eq!(4, letters.len());
+        assert_eq!(25, letters.total());
+        assert_eq!(vec![(&'B', 11)], letters.most_common(1));
+        assert_eq!(vec![(&'H', 4)], letters.least_common(1));
+        assert_eq!(7, letters.spread());
+
+        letters.subtract('H', 10);
+        assert_eq!(0, letters.get(&'H'));
+        assert_eq!(3, letters.len());
+
+        letters.scale(1 << 100);
+        assert_eq!(11 << 100, letters.get(&'B'));
+
+        let sum = Counter::from_counts([('a', 2), ('b', 1)]) + "ab".chars().collect();
+        assert_eq!(3, sum.get(&'a'));
+        let difference = sum - "aaaab".chars().collect();
+        assert_eq!(vec![(&'b', 1)], difference.most_common(5));
+
+        // Items that are pairs are still counted one at a time
+        let pairs = [('a', 2), ('a', 2)].into_iter().collect::<Counter<_>>();
+        assert_eq!(2, pairs.get(&('a', 2)));
+    }
+}
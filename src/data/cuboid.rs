@@ -0,0 +1,236 @@
+use super::Interval;
+use crate::tools::MoreItertools;
+use std::str::FromStr;
+
+/// A box with `N` dimensions, made of one interval along each axis. Everything works out volumes
+/// and overlaps from the corners, so the boxes can be enormous without costing any more.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Cuboid<const N: usize> {
+    axes: [Interval; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    pub const fn new(axes: [Interval; N]) -> Self {
+        Cuboid { axes }
+    }
+
+    /// The interval along each axis.
+    pub fn axes(&self) -> &[Interval; N] {
+        &self.axes
+    }
+
+    /// How many points are inside the box.
+    pub fn volume(&self) -> u128 {
+        self.axes
+            .iter()
+            .map(|interval| interval.len() as u128)
+            .product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(|interval| interval.is_empty())
+    }
+
+    pub fn contains(&self, point: [isize; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(interval, value)| interval.contains(value))
+    }
+
+    /// Whether every point in this box is also in the other one.
+    pub fn is_subset(&self, other: &Cuboid<N>) -> bool {
+        self.is_empty()
+            || self
+                .axes
+                .iter()
+                .zip(&other.axes)
+                .all(|(lhs, &rhs)| lhs.is_subset(rhs))
+    }
+
+    pub fn overlaps(&self, other: &Cuboid<N>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The box where both boxes overlap, if there is one.
+    pub fn intersection(&self, other: &Cuboid<N>) -> Option<Cuboid<N>> {
+        let mut axes = self.axes;
+        for (axis, &other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(Cuboid { axes })
+    }
+
+    /// Cut the other box out of this one, giving the pieces that are left over. The pieces don't
+    /// overlap each other, and there are at most two for each axis.
+    pub fn subtract(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        let overlap = match self.intersection(other) {
+            Some(overlap) => overlap,
+            None if self.is_empty() => return vec![],
+            None => return vec![*self],
+        };
+
+        // Slice off the bits above and below the overlap one axis at a time, and then shrink what's
+        // left to the overlap on that axis before moving onto the next one.
+        let mut output = Vec::new();
+        let mut remaining = *self;
+        for (axis, &cut) in overlap.axes.iter().enumerate() {
+            for piece in remaining.axes[axis].subtract(cut) {
+                let mut slice = remaining;
+                slice.axes[axis] = piece;
+                output.push(slice);
+            }
+            remaining.axes[axis] = cut;
+        }
+        output
+    }
+
+    /// The points that are in either box, as a set of boxes that don't overlap each other.
+    pub fn union(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        let mut output = other.subtract(self);
+        if !self.is_empty() {
+            output.push(*self);
+        }
+        output
+    }
+}
+
+/// Parse boxes like `x=10..12,y=10..12,z=10..12`, where each interval includes both ends. The axis
+/// names are optional, and are ignored if they're there - the axes just go in order.
+impl<const N: usize> FromStr for Cuboid<N> {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let axes = s
+            .split(',')
+            .map(|axis| axis.split_once('=').map_or(axis, |(_, interval)| interval))
+            .map(str::trim)
+            .parsed()
+            .try_collect_array()?;
+        Ok(Cuboid { axes })
+    }
+}
+
+/// A set of points made of boxes that never overlap each other, so its volume is just the sum of
+/// theirs. Boxes can be switched on and off, like the reactor cores that need turning on and off
+/// in huge chunks.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Region<const N: usize> {
+    cuboids: Vec<Cuboid<N>>,
+}
+
+impl<const N: usize> Default for Region<N> {
+    fn default() -> Self {
+        Region::new()
+    }
+}
+
+impl<const N: usize> Region<N> {
+    /// Make a new, empty, region.
+    pub fn new() -> Self {
+        Region { cuboids: vec![] }
+    }
+
+    /// Add every point in a box to the region.
+    pub fn add(&mut self, cuboid: Cuboid<N>) {
+        self.remove(cuboid);
+        if !cuboid.is_empty() {
+            self.cuboids.push(cuboid);
+        }
+    }
+
+    /// Take every point in a box out of the region.
+    pub fn remove(&mut self, cuboid: Cuboid<N>) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|existing| existing.subtract(&cuboid))
+            .collect();
+    }
+
+    /// How many points are in the region.
+    pub fn volume(&self) -> u128 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    pub fn contains(&self, point: [isize; N]) -> bool {
+        self.cuboids.iter().any(|cuboid| cuboid.contains(point))
+    }
+
+    /// The boxes that make up the region, which don't overlap each other.
+    pub fn cuboids(&self) -> &[Cuboid<N>] {
+        &self.cuboids
+    }
+}
+
+impl<const N: usize> FromIterator<Cuboid<N>> for Region<N> {
+    fn from_iter<I: IntoIterator<Item = Cuboid<N>>>(iter: I) -> Self {
+        let mut output = Region::new();
+        for cuboid in iter {
+            output.add(cuboid);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cuboid, Region};
+    use crate::data::{Interval, Point, Segment};
+
+    #[test]
+    fn does_cuboid_algebra() {
+        let a = "x=10..12,y=10..12,z=10..12".parse::<Cuboid<3>>().unwrap();
+        let b = "11..13,11..13,11..13".parse::<Cuboid<3>>().unwrap();
+        assert_eq!(27, a.volume());
+        assert_eq!(8, a.intersection(&b).unwrap().volume());
+
+        let pieces = a.subtract(&b);
+        assert_eq!(27 - 8, pieces.iter().map(Cuboid::volume).sum::<u128>());
+        assert_eq!(46, a.union(&b).iter().map(Cuboid::volume).sum::<u128>());
+        assert!(!pieces.iter().any(|piece| piece.overlaps(&b)));
+        assert!(pieces.iter().all(|piece| piece.is_subset(&a)));
+
+        "x=1..2,y=3..4".parse::<Cuboid<3>>().unwrap_err();
+    }
+
+    #[test]
+    fn switches_regions_on_and_off() {
+        let mut region = Region::new();
+        region.add("x=10..12,y=10..12,z=10..12".parse::<Cuboid<3>>().unwrap());
+        region.add("x=11..13,y=11..13,z=11..13".parse().unwrap());
+        region.remove("x=9..11,y=9..11,z=9..11".parse().unwrap());
+        region.add("x=10..10,y=10..10,z=10..10".parse().unwrap());
+        assert_eq!(39, region.volume());
+        assert!(region.contains([13, 13, 13]));
+        assert!(!region.contains([11, 11, 11]));
+    }
+
+    #[test]
+    fn counts_overlaps_without_visiting_points() {
+        // The straight vent lines from day five, as boxes that are one point thick
+        let vents = [
+            "0,9 -> 5,9",
+            "9,4 -> 3,4",
+            "2,2 -> 2,1",
+            "7,0 -> 7,4",
+            "0,9 -> 2,9",
+            "3,4 -> 1,4",
+        ]
+        .map(|line| {
+            let segment = line.parse::<Segment>().unwrap();
+            let (Point(x1, y1), Point(x2, y2)) = (segment.start(), segment.end());
+            Cuboid::new([
+                Interval::from(x1.min(x2)..=x1.max(x2)),
+                Interval::from(y1.min(y2)..=y1.max(y2)),
+            ])
+        });
+
+        let overlaps = vents
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| vents[i + 1..].iter().filter_map(move |b| a.intersection(b)))
+            .collect::<Region<2>>();
+        assert_eq!(5, overlaps.volume());
+    }
+}
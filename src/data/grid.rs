@@ -0,0 +1,280 @@
+use super::{Boundary, Coordinate, Neighbours, Offsets, Point};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// A rectangular grid of values, stored row by row. Coordinates are `(x, y)`, where `x` is the
+/// column and `y` is the row, so `(0, 0)` is the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Make a new grid, with every cell set to the same value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Make a new grid, calling a function to work out the value of each cell.
+    pub fn from_fn(width: usize, height: usize, f: impl FnMut(Coordinate) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coordinate(x, y)))
+            .map(f)
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Make a new grid out of a set of rows, which must all be the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> eyre::Result<Self> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for row in rows {
+            match width {
+                None => width = Some(row.len()),
+                Some(width) => eyre::ensure!(
+                    row.len() == width,
+                    "All rows must be the same length, but row {} has length {} instead of {}",
+                    height,
+                    row.len(),
+                    width
+                ),
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        let width = width.ok_or_else(|| eyre::format_err!("No rows found"))?;
+        eyre::ensure!(width != 0, "Rows must not be empty");
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of cells in the grid.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The corner of the grid, just outside of it, for use with the neighbour functions on
+    /// `Coordinate`.
+    pub fn max_point(&self) -> Coordinate {
+        Coordinate(self.width, self.height)
+    }
+
+    /// Check whether a coordinate lies within the grid.
+    pub fn contains(&self, Coordinate(x, y): Coordinate) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, coord: Coordinate) -> Option<&T> {
+        if self.contains(coord) {
+            Some(&self.cells[self.offset(coord)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coordinate) -> Option<&mut T> {
+        if self.contains(coord) {
+            let offset = self.offset(coord);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over every coordinate in the grid, row by row.
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coordinate(x, y)))
+    }
+
+    /// Iterate over every cell in the grid (and where it is), row by row.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Coordinate, &T)> {
+        self.coordinates().zip(self.cells.iter())
+    }
+
+    /// Iterate over every cell in the grid, row by row.
+    pub fn values(&self) -> impl '_ + Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Iterate over every cell in the grid mutably, row by row.
+    pub fn values_mut(&mut self) -> impl '_ + Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Get a single row of the grid. Panics if it's out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterate over the rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl '_ + Iterator<Item = &[T]> {
+        // Not `chunks`, since that panics on a grid with no columns
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Iterate down a single column of the grid. Panics if it's out of bounds.
+    pub fn column(&self, x: usize) -> impl '_ + Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is out of bounds", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Iterate over the columns of the grid, from left to right.
+    pub fn columns(&self) -> impl '_ + Iterator<Item = impl '_ + Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Move a coordinate by an offset, where the boundary decides what happens at the edges.
+    pub fn step(&self, coord: Coordinate, delta: Point, boundary: Boundary) -> Option<Coordinate> {
+        coord.step(delta, self.max_point(), boundary)
+    }
+
+    /// The neighbours of a cell, where the offsets decide what counts as a neighbour and the
+    /// boundary decides what happens at the edges.
+    pub fn neighbours<'a>(
+        &self,
+        coord: Coordinate,
+        offsets: Offsets<'a>,
+        boundary: Boundary,
+    ) -> Neighbours<'a> {
+        coord.neighbours(self.max_point(), offsets, boundary)
+    }
+
+    /// The neighbours of a cell that lie within the grid, not including diagonals.
+    pub fn manhattan_neighbours(&self, coord: Coordinate) -> Neighbours<'static> {
+        coord.manhattan_neighbours(self.max_point())
+    }
+
+    /// The neighbours of a cell that lie within the grid, including diagonals.
+    pub fn all_neighbours(&self, coord: Coordinate) -> Neighbours<'static> {
+        coord.all_neighbours(self.max_point())
+    }
+
+    /// Make a new grid of the same size, by running a function over every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset(&self, Coordinate(x, y): Coordinate) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coordinate) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, coord: Coordinate) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", coord))
+    }
+}
+
+/// Parse a grid where every character is a cell, like a block of digits.
+impl<T: FromStr> FromStr for Grid<T>
+where
+    eyre::Report: From<T::Err>,
+{
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.chars()
+                    .map(|ch| {
+                        let mut tmp = [0u8; 4];
+                        let string = ch.encode_utf8(&mut tmp);
+                        Ok(string.parse::<T>()?)
+                    })
+                    .collect::<eyre::Result<Vec<_>>>()
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Grid::from_rows(rows)
+    }
+}
+
+/// Print the grid out one row per line, with nothing between the cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Coordinate, Grid};
+
+    #[test]
+    fn indexes_row_major() {
+        let grid = "123\n456\n".parse::<Grid<u8>>().unwrap();
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(6, grid[Coordinate(2, 1)]);
+        assert_eq!(None, grid.get(Coordinate(3, 0)));
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!("123\n456\n", grid.to_string());
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        "123\n45\n".parse::<Grid<u8>>().unwrap_err();
+        Grid::<u8>::from_rows(vec![]).unwrap_err();
+    }
+}
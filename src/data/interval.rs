@@ -0,0 +1,163 @@
+use crate::tools::StringTools;
+use std::{
+    fmt,
+    ops::{Range, RangeInclusive},
+    str::FromStr,
+};
+
+/// A range of whole numbers. It's stored like a `Range` (so `start` is included and `end` isn't),
+/// but it's written and parsed like the puzzles write them, where `a..b` includes both ends.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Interval {
+    start: isize,
+    end: isize,
+}
+
+impl Interval {
+    /// Make a new interval, from `start` up to (but not including) `end`. It's empty if `end` isn't
+    /// after `start`.
+    pub const fn new(start: isize, end: isize) -> Self {
+        Interval { start, end }
+    }
+
+    /// Make a new interval that includes both ends.
+    pub const fn inclusive(start: isize, last: isize) -> Self {
+        Interval::new(start, last + 1)
+    }
+
+    pub fn start(self) -> isize {
+        self.start
+    }
+
+    /// The first number after the interval.
+    pub fn end(self) -> isize {
+        self.end
+    }
+
+    /// How many numbers are in the interval.
+    pub fn len(self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.end.abs_diff(self.start) as u64
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(self, value: isize) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every number in this interval is also in the other one.
+    pub fn is_subset(self, other: Interval) -> bool {
+        self.is_empty() || (other.start <= self.start && self.end <= other.end)
+    }
+
+    pub fn overlaps(self, other: Interval) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The numbers that are in both intervals, if there are any.
+    pub fn intersection(self, other: Interval) -> Option<Interval> {
+        let output = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!output.is_empty()).then_some(output)
+    }
+
+    /// The numbers that are in either interval. If they overlap or touch this is a single interval,
+    /// and otherwise it's both of them, in order.
+    pub fn union(self, other: Interval) -> Vec<Interval> {
+        Interval::merge(vec![self, other])
+    }
+
+    /// The pieces of this interval that aren't in the other one. There can be up to two of them,
+    /// if the other interval is in the middle of this one.
+    pub fn subtract(self, other: Interval) -> impl Iterator<Item = Interval> {
+        let below = Interval::new(self.start, self.end.min(other.start));
+        let above = Interval::new(self.start.max(other.end), self.end);
+        [below, above].into_iter().filter(|piece| !piece.is_empty())
+    }
+
+    /// Tidy up a set of intervals into the smallest set of intervals that cover the same numbers,
+    /// sorted in order. Intervals that overlap or touch get combined.
+    pub fn merge(intervals: impl IntoIterator<Item = Interval>) -> Vec<Interval> {
+        let mut intervals = intervals
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_unstable();
+
+        let mut output: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match output.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => output.push(interval),
+            }
+        }
+        output
+    }
+}
+
+impl From<Range<isize>> for Interval {
+    fn from(range: Range<isize>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl From<RangeInclusive<isize>> for Interval {
+    fn from(range: RangeInclusive<isize>) -> Self {
+        Interval::inclusive(*range.start(), *range.end())
+    }
+}
+
+/// Parse the `a..b` notation, which includes both ends.
+impl FromStr for Interval {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, last): (isize, isize) = s.split_parse("..")?;
+        eyre::ensure!(start <= last, "The interval {:?} runs backwards", s);
+        Ok(Interval::inclusive(start, last))
+    }
+}
+
+/// Write the interval out in the same `a..b` notation used for parsing, which includes both ends.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end - 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Interval;
+
+    #[test]
+    fn does_interval_algebra() {
+        let a = "-2..5".parse::<Interval>().unwrap();
+        let b = Interval::from(3..10);
+        assert_eq!(8, a.len());
+        assert_eq!("-2..5", a.to_string());
+        assert_eq!(Some(Interval::from(3..=5)), a.intersection(b));
+        assert_eq!(vec![Interval::new(-2, 10)], a.union(b));
+        assert_eq!(
+            vec![Interval::new(-2, 3)],
+            a.subtract(b).collect::<Vec<_>>()
+        );
+        assert_eq!(2, b.subtract(Interval::from(5..7)).count());
+        assert_eq!(0, b.subtract(a.union(b)[0]).count());
+        assert!(Interval::from(4..6).is_subset(b));
+        "5..4".parse::<Interval>().unwrap_err();
+
+        let merged = Interval::merge(vec![
+            Interval::from(8..10),
+            Interval::from(0..2),
+            Interval::from(2..3),
+            Interval::from(1..1),
+            Interval::from(9..12),
+        ]);
+        assert_eq!(vec![Interval::from(0..3), Interval::from(8..12)], merged);
+    }
+}
@@ -1,3 +1,29 @@
+mod bit_set;
+mod bit_string;
 mod coordinate;
+mod counter;
+mod cuboid;
+mod grid;
+mod interval;
+mod neighbours;
+mod point;
+mod point3;
+mod regions;
+mod rotation;
+mod segment;
+mod sparse_grid;
 
-pub use coordinate::Coordinate;
\ No newline at end of file
+pub use bit_set::{BitSet, Bits};
+pub use bit_string::BitString;
+pub use coordinate::Coordinate;
+pub use counter::Counter;
+pub use cuboid::{Cuboid, Region};
+pub use grid::Grid;
+pub use interval::Interval;
+pub use neighbours::{Boundary, Neighbours, Offsets};
+pub use point::Point;
+pub use point3::Point3;
+pub use regions::{RegionStats, Regions};
+pub use rotation::{shared_fingerprints, Rotation};
+pub use segment::{Intersection, Segment};
+pub use sparse_grid::SparseGrid;
\ No newline at end of file
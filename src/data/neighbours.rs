@@ -0,0 +1,167 @@
+use super::{Coordinate, Point};
+use std::slice;
+
+/// The offsets to a point's neighbours, which decide what counts as a neighbour in the first place.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Offsets<'a>(pub &'a [Point]);
+
+impl Offsets<'static> {
+    /// The four neighbours that share an edge with a point.
+    pub const MANHATTAN: Offsets<'static> =
+        Offsets(&[Point(-1, 0), Point(1, 0), Point(0, -1), Point(0, 1)]);
+
+    /// All eight neighbours around a point, including the diagonals.
+    pub const ALL: Offsets<'static> = Offsets(&[
+        Point(-1, -1),
+        Point(-1, 0),
+        Point(-1, 1),
+        Point(0, -1),
+        Point(0, 1),
+        Point(1, -1),
+        Point(1, 0),
+        Point(1, 1),
+    ]);
+}
+
+/// What happens when a step would take a coordinate off the edge of a grid.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Boundary {
+    /// The edges are walls, and anything past them doesn't exist.
+    Clip,
+    /// The edges join up with the opposite side, like on a torus. On really small grids, this means
+    /// a point can be its own neighbour, or be the same neighbour more than once.
+    Wrap,
+}
+
+impl Boundary {
+    /// Move a coordinate by an offset within the grid defined by the corner points `(0,0)` and
+    /// `max_point`, dealing with the edges of the grid according to this policy.
+    pub fn step(
+        self,
+        coord: Coordinate,
+        delta: Point,
+        max_point: Coordinate,
+    ) -> Option<Coordinate> {
+        let Coordinate(max_x, max_y) = max_point;
+        match self {
+            Boundary::Clip => coord
+                .offset(delta)
+                .filter(|&Coordinate(x, y)| x < max_x && y < max_y),
+            Boundary::Wrap => {
+                if max_x == 0 || max_y == 0 {
+                    return None;
+                }
+                let Point(x, y) = Point::from(coord) + delta;
+                let x = x.rem_euclid(max_x as isize) as usize;
+                let y = y.rem_euclid(max_y as isize) as usize;
+                Some(Coordinate(x, y))
+            }
+        }
+    }
+}
+
+/// See `Coordinate::neighbours`.
+#[derive(Debug, Clone)]
+pub struct Neighbours<'a> {
+    centre: Coordinate,
+    max_point: Coordinate,
+    offsets: slice::Iter<'a, Point>,
+    boundary: Boundary,
+}
+
+impl<'a> Neighbours<'a> {
+    pub(super) fn new(
+        centre: Coordinate,
+        max_point: Coordinate,
+        offsets: Offsets<'a>,
+        boundary: Boundary,
+    ) -> Self {
+        Neighbours {
+            centre,
+            max_point,
+            offsets: offsets.0.iter(),
+            boundary,
+        }
+    }
+}
+
+impl<'a> Iterator for Neighbours<'a> {
+    type Item = Coordinate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Neighbours {
+            centre,
+            max_point,
+            boundary,
+            ..
+        } = *self;
+        self.offsets
+            .find_map(|&delta| boundary.step(centre, delta, max_point))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.offsets.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Boundary, Offsets};
+    use crate::data::{Coordinate, Grid, Point};
+
+    #[test]
+    fn clips_to_the_grid() {
+        let max_point = Coordinate(3, 3);
+        let corner = Coordinate(0, 0)
+            .all_neighbours(max_point)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Coordinate(0, 1), Coordinate(1, 0), Coordinate(1, 1)],
+            corner
+        );
+
+        let middle = Coordinate(1, 1).manhattan_neighbours(max_point).count();
+        assert_eq!(4, middle);
+    }
+
+    #[test]
+    fn uses_custom_offsets() {
+        let knight = Offsets(&[Point(1, 2), Point(2, 1), Point(-1, 2), Point(-2, -1)]);
+        let moves = Coordinate(1, 0)
+            .neighbours(Coordinate(8, 8), knight, Boundary::Clip)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Coordinate(2, 2), Coordinate(3, 1), Coordinate(0, 2)],
+            moves
+        );
+    }
+
+    #[test]
+    fn wraps_around_the_edges() {
+        let max_point = Coordinate(4, 3);
+        let corner = Coordinate(0, 0)
+            .neighbours(max_point, Offsets::MANHATTAN, Boundary::Wrap)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Coordinate(3, 0),
+                Coordinate(1, 0),
+                Coordinate(0, 2),
+                Coordinate(0, 1)
+            ],
+            corner
+        );
+
+        let grid = Grid::new(4, 3, 0);
+        let right = Point(1, 0);
+        assert_eq!(None, grid.step(Coordinate(3, 2), right, Boundary::Clip));
+        assert_eq!(
+            Some(Coordinate(0, 2)),
+            grid.step(Coordinate(3, 2), right, Boundary::Wrap)
+        );
+        assert_eq!(
+            Some(Coordinate(0, 2)),
+            Coordinate(3, 2).step(right * 5, max_point, Boundary::Wrap)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use super::{Coordinate, Offsets};
+use crate::tools::StringTools;
+use std::{
+    convert::TryFrom,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// An (x, y) point that you can do maths with. Unlike `Coordinate` this can go negative (as long as
+/// `T` can), so it's good for positions that wander about, and for the offsets between points.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Point<T = isize>(pub T, pub T);
+
+impl<T: FromStr> FromStr for Point<T>
+where
+    eyre::Report: From<T::Err>,
+{
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_parse(",")?;
+        Ok(Point(x, y))
+    }
+}
+
+/// The distance between two values, without needing them to be signed.
+fn abs_diff<T: Ord + Sub<Output = T>>(lhs: T, rhs: T) -> T {
+    if lhs > rhs {
+        lhs - rhs
+    } else {
+        rhs - lhs
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// The distance between two points, if you can only move along the axes.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.0, other.0) + abs_diff(self.1, other.1)
+    }
+
+    /// The distance between two points, if you can move diagonally as well.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        abs_diff(self.0, other.0).max(abs_diff(self.1, other.1))
+    }
+}
+
+impl Point {
+    /// Find all of the neighbours of this point, where the offsets decide what counts as a
+    /// neighbour. There are no edges to worry about, so every offset gives a neighbour.
+    pub fn neighbours<'a>(self, offsets: Offsets<'a>) -> impl 'a + Iterator<Item = Point> {
+        offsets.0.iter().map(move |&delta| self + delta)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, Point(x, y): Self) -> Self::Output {
+        Point(self.0 + x, self.1 + y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, Point(x, y): Self) -> Self::Output {
+        Point(self.0 - x, self.1 - y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point(-self.0, -self.1)
+    }
+}
+
+/// Scale a point by a number.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self::Output {
+        Point(self.0 * scale, self.1 * scale)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Default + Add<Output = T>> Sum for Point<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Point::default(), |lhs, rhs| lhs + rhs)
+    }
+}
+
+impl From<Coordinate> for Point {
+    fn from(Coordinate(x, y): Coordinate) -> Self {
+        Point(x as isize, y as isize)
+    }
+}
+
+impl TryFrom<Point> for Coordinate {
+    type Error = eyre::Report;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        let Point(x, y) = point;
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => Ok(Coordinate(x, y)),
+            _ => eyre::bail!("{:?} has a negative position, so it isn't a coordinate", point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Point;
+    use crate::data::Coordinate;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point(3, -4);
+        let b = "-1,2".parse::<Point>().unwrap();
+        assert_eq!(Point(-1, 2), b);
+        assert_eq!(Point(2, -2), a + b);
+        assert_eq!(Point(4, -6), a - b);
+        assert_eq!(Point(-3, 4), -a);
+        assert_eq!(Point(6, -8), a * 2);
+        assert_eq!(10, a.manhattan_distance(b));
+        assert_eq!(6, a.chebyshev_distance(b));
+        assert_eq!(5u32, Point(1u32, 7).manhattan_distance(Point(4, 5)));
+    }
+
+    #[test]
+    fn converts_coordinates() {
+        assert_eq!(Point(3, 4), Point::from(Coordinate(3, 4)));
+        assert_eq!(Coordinate(3, 4), Coordinate::try_from(Point(3, 4)).unwrap());
+        Coordinate::try_from(Point(-1, 4)).unwrap_err();
+    }
+}
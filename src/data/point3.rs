@@ -0,0 +1,102 @@
+use crate::tools::MoreItertools;
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// An (x, y, z) point that you can do maths with. See `Point` for the two dimensional version.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Point3<T = isize>(pub T, pub T, pub T);
+
+impl<T: FromStr> FromStr for Point3<T>
+where
+    eyre::Report: From<T::Err>,
+{
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = s.split(',').parsed().try_collect_array()?;
+        Ok(Point3(x, y, z))
+    }
+}
+
+impl<T> Point3<T> {
+    /// The three axes, as an array.
+    pub fn to_array(self) -> [T; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    pub fn from_array([x, y, z]: [T; 3]) -> Self {
+        Point3(x, y, z)
+    }
+}
+
+impl Point3 {
+    /// The distance between two points, if you can only move along the axes.
+    pub fn manhattan_distance(self, other: Self) -> isize {
+        let Point3(x, y, z) = self - other;
+        x.abs() + y.abs() + z.abs()
+    }
+
+    /// Describe the offset between two points in a way that doesn't change when both of them are
+    /// moved or rotated together, by throwing away the direction and the order of the axes. Two sets
+    /// of points that share a lot of fingerprints are likely to be the same points, seen from
+    /// different places.
+    pub fn fingerprint(self, other: Self) -> [isize; 3] {
+        let mut output = (self - other).to_array().map(isize::abs);
+        output.sort_unstable();
+        output
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, Point3(x, y, z): Self) -> Self::Output {
+        Point3(self.0 + x, self.1 + y, self.2 + z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, Point3(x, y, z): Self) -> Self::Output {
+        Point3(self.0 - x, self.1 - y, self.2 - z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point3(-self.0, -self.1, -self.2)
+    }
+}
+
+/// Scale a point by a number.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self::Output {
+        Point3(self.0 * scale, self.1 * scale, self.2 * scale)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Default + Add<Output = T>> Sum for Point3<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Point3::default(), |lhs, rhs| lhs + rhs)
+    }
+}
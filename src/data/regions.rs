@@ -0,0 +1,210 @@
+use super::{Boundary, Coordinate, Grid, Offsets};
+use std::collections::VecDeque;
+
+/// Every cell in a grid labelled with the region it's in, where a region is a group of cells that
+/// you can get between by stepping onto neighbours that connect. See `Grid::label_regions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    labels: Grid<usize>,
+    stats: Vec<RegionStats>,
+}
+
+/// What a region looks like from the outside.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RegionStats {
+    /// The number of cells in the region.
+    pub size: usize,
+    /// The first cell of the region, going row by row.
+    pub seed: Coordinate,
+    /// The top left corner of the box around the region.
+    pub min: Coordinate,
+    /// The bottom right corner of the box around the region (which is part of the box).
+    pub max: Coordinate,
+}
+
+impl Regions {
+    /// Which region a cell is in. Regions are numbered from zero, in the order that their first
+    /// cell comes in going row by row. Panics if the cell isn't in the grid.
+    pub fn label(&self, coord: Coordinate) -> usize {
+        self.labels[coord]
+    }
+
+    /// The label for every cell.
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+
+    /// What a region looks like, given its label.
+    pub fn get(&self, label: usize) -> Option<&RegionStats> {
+        self.stats.get(label)
+    }
+
+    /// Every region, where the index of each one is its label.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = &RegionStats> {
+        self.stats.iter()
+    }
+
+    /// The number of regions.
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    /// Every cell in a region, going row by row.
+    pub fn cells(&self, label: usize) -> impl '_ + Iterator<Item = Coordinate> {
+        self.labels
+            .iter()
+            .filter(move |&(_, &other)| other == label)
+            .map(|(coord, _)| coord)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Find every cell you can get to from `start`, nearest first, by stepping to neighbours
+    /// wherever `connects(from, to)` says the two cells' values join up. This doesn't recurse, so
+    /// it's fine on huge regions.
+    pub fn flood_fill(
+        &self,
+        start: Coordinate,
+        offsets: Offsets<'_>,
+        boundary: Boundary,
+        mut connects: impl FnMut(&T, &T) -> bool,
+    ) -> Vec<Coordinate> {
+        if !self.contains(start) {
+            return vec![];
+        }
+
+        let mut seen = Grid::new(self.width(), self.height(), false);
+        seen[start] = true;
+        let mut queue = VecDeque::from([start]);
+        let mut output = vec![];
+        while let Some(current) = queue.pop_front() {
+            output.push(current);
+            for next in self.neighbours(current, offsets, boundary) {
+                if !seen[next] && connects(&self[current], &self[next]) {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        output
+    }
+
+    /// Split the whole grid up into regions, where neighbouring cells are in the same region if
+    /// `connects(from, to)` says their values join up. Every cell ends up in exactly one region,
+    /// even if it's on its own - so any cells that shouldn't be in a region (walls, say) come out
+    /// as lots of regions of size one, which are easy enough to filter out with `seed`.
+    pub fn label_regions(
+        &self,
+        offsets: Offsets<'_>,
+        boundary: Boundary,
+        mut connects: impl FnMut(&T, &T) -> bool,
+    ) -> Regions {
+        const UNLABELLED: usize = usize::MAX;
+        let mut labels = Grid::new(self.width(), self.height(), UNLABELLED);
+        let mut stats = vec![];
+        let mut queue = VecDeque::new();
+
+        for seed in self.coordinates() {
+            if labels[seed] != UNLABELLED {
+                continue;
+            }
+
+            let label = stats.len();
+            let mut region = RegionStats {
+                size: 0,
+                seed,
+                min: seed,
+                max: seed,
+            };
+            labels[seed] = label;
+            queue.push_back(seed);
+            while let Some(current) = queue.pop_front() {
+                region.size += 1;
+                region.min = Coordinate(region.min.0.min(current.0), region.min.1.min(current.1));
+                region.max = Coordinate(region.max.0.max(current.0), region.max.1.max(current.1));
+                for next in self.neighbours(current, offsets, boundary) {
+                    if labels[next] == UNLABELLED && connects(&self[current], &self[next]) {
+                        labels[next] = label;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            stats.push(region);
+        }
+
+        Regions { labels, stats }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        data::{Boundary, Coordinate, Grid, Offsets},
+        tools::StringTools,
+    };
+
+    fn get_grid() -> Grid<char> {
+        r"
+            aab.c
+            .ab.c
+            ....c
+            dd..c
+        "
+        .dedent()
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn fills_regions() {
+        let grid = get_grid();
+        let same = |a: &char, b: &char| a == b;
+        let filled = grid.flood_fill(Coordinate(0, 0), Offsets::MANHATTAN, Boundary::Clip, same);
+        assert_eq!(
+            vec![Coordinate(0, 0), Coordinate(1, 0), Coordinate(1, 1)],
+            filled
+        );
+
+        let dots = grid.flood_fill(Coordinate(3, 0), Offsets::MANHATTAN, Boundary::Clip, same);
+        assert_eq!(9, dots.len());
+
+        // With wrapping, the c's on the right join up with the left hand side
+        let wrapped = grid.flood_fill(Coordinate(0, 3), Offsets::MANHATTAN, Boundary::Wrap, same);
+        assert_eq!(2, wrapped.len());
+        let wrapped = grid.flood_fill(Coordinate(3, 1), Offsets::MANHATTAN, Boundary::Wrap, same);
+        assert_eq!(9, wrapped.len());
+    }
+
+    #[test]
+    fn labels_regions() {
+        let grid = get_grid();
+        let regions = grid.label_regions(Offsets::MANHATTAN, Boundary::Clip, |a, b| a == b);
+        assert_eq!(5, regions.len());
+        assert_eq!(
+            regions.label(Coordinate(0, 0)),
+            regions.label(Coordinate(1, 1))
+        );
+        assert_eq!(1, regions.label(Coordinate(2, 0)));
+
+        let c = regions.get(regions.label(Coordinate(4, 0))).unwrap();
+        assert_eq!(4, c.size);
+        assert_eq!((Coordinate(4, 0), Coordinate(4, 3)), (c.min, c.max));
+
+        let dots = regions.get(regions.label(Coordinate(0, 1))).unwrap();
+        assert_eq!(Coordinate(3, 0), dots.seed);
+        assert_eq!((Coordinate(0, 0), Coordinate(3, 3)), (dots.min, dots.max));
+        assert_eq!(
+            vec![Coordinate(0, 3), Coordinate(1, 3)],
+            regions.cells(4).collect::<Vec<_>>()
+        );
+
+        // Diagonals join the b up with the dots
+        let regions =
+            grid.label_regions(Offsets::ALL, Boundary::Clip, |a, b| a != &'a' && b != &'a');
+        assert_eq!(4, regions.len());
+    }
+}
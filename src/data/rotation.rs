@@ -0,0 +1,195 @@
+use super::{Counter, Point3};
+use std::{collections::HashMap, ops::Neg};
+
+/// One of the 24 ways to turn something around in three dimensions, without mirroring it. Each
+/// axis of the output comes from one axis of the input, possibly flipped.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Rotation {
+    /// Which input axis each output axis is taken from.
+    axes: [usize; 3],
+    /// Whether each output axis is flipped.
+    flipped: [bool; 3],
+}
+
+impl Rotation {
+    /// The rotation that leaves everything where it is.
+    pub const IDENTITY: Rotation = Rotation {
+        axes: [0, 1, 2],
+        flipped: [false; 3],
+    };
+
+    /// All 24 rotations, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [0, 2, 1],
+            [2, 1, 0],
+            [1, 0, 2],
+        ];
+
+        PERMUTATIONS
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, &axes)| {
+                // The first three permutations are even, and the last three are odd. Every odd
+                // permutation is a mirror image, so needs an odd number of flips to undo it.
+                let odd = idx >= 3;
+                (0..8u8).map(move |flips| {
+                    let flipped = [flips & 4 != 0, flips & 2 != 0, flips & 1 != 0];
+                    (axes, flipped, odd)
+                })
+            })
+            .filter(|(_, flipped, odd)| {
+                let flip_count = flipped.iter().filter(|&&flip| flip).count();
+                (flip_count % 2 == 1) == *odd
+            })
+            .map(|(axes, flipped, _)| Rotation { axes, flipped })
+    }
+
+    /// Rotate a point.
+    pub fn apply<T: Copy + Neg<Output = T>>(self, point: Point3<T>) -> Point3<T> {
+        let input = point.to_array();
+        let output = [0, 1, 2].map(|i| {
+            let value = input[self.axes[i]];
+            if self.flipped[i] {
+                -value
+            } else {
+                value
+            }
+        });
+
+        Point3::from_array(output)
+    }
+
+    /// A single rotation that does the same thing as doing this rotation, and then the next one.
+    pub fn then(self, next: Rotation) -> Rotation {
+        Rotation {
+            axes: [0, 1, 2].map(|i| self.axes[next.axes[i]]),
+            flipped: [0, 1, 2].map(|i| next.flipped[i] ^ self.flipped[next.axes[i]]),
+        }
+    }
+
+    /// The rotation that undoes this one.
+    pub fn inverse(self) -> Rotation {
+        let mut output = Rotation::IDENTITY;
+        for i in 0..3 {
+            output.axes[self.axes[i]] = i;
+            output.flipped[self.axes[i]] = self.flipped[i];
+        }
+        output
+    }
+
+    /// Try to line up a set of points with a reference set, where both sets are the same points but
+    /// seen from different positions and facing different ways. If at least `threshold` of the
+    /// points can be made to match up, this returns the rotation and offset that does it, such that
+    /// `rotation.apply(point) + offset` moves a point into the reference's frame.
+    pub fn align(
+        reference: &[Point3],
+        other: &[Point3],
+        threshold: usize,
+    ) -> Option<(Rotation, Point3)> {
+        // Quick check first: if enough points are shared, then so are the distances between them
+        if threshold > 1 {
+            let needed = threshold * (threshold - 1) / 2;
+            if shared_fingerprints(reference, other) < needed {
+                return None;
+            }
+        }
+
+        Rotation::all().find_map(|rotation| {
+            let mut votes = HashMap::new();
+            for &point in other {
+                let rotated = rotation.apply(point);
+                for &target in reference {
+                    *votes.entry(target - rotated).or_insert(0) += 1;
+                }
+            }
+
+            votes
+                .into_iter()
+                .find(|&(_, count)| count >= threshold)
+                .map(|(offset, _)| (rotation, offset))
+        })
+    }
+}
+
+/// Count how many of the fingerprints (see `Point3::fingerprint`) between pairs of points in one set
+/// also show up between pairs of points in another. Symmetric shapes have lots of pairs with the
+/// same fingerprint, so each fingerprint counts as many times as it's in both sets.
+pub fn shared_fingerprints(lhs: &[Point3], rhs: &[Point3]) -> usize {
+    let fingerprints = |points: &[Point3]| {
+        points
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| points[i + 1..].iter().map(move |&b| a.fingerprint(b)))
+            .collect::<Counter<_>>()
+    };
+
+    let rhs = fingerprints(rhs);
+    fingerprints(lhs)
+        .iter()
+        .map(|(fingerprint, count)| count.min(rhs.get(fingerprint)) as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rotation;
+    use crate::data::Point3;
+    use std::collections::HashSet;
+
+    #[test]
+    fn finds_all_rotations() {
+        let point = Point3(1, 2, 3);
+        let rotated = Rotation::all()
+            .map(|rotation| rotation.apply(point))
+            .collect::<HashSet<_>>();
+        assert_eq!(24, rotated.len());
+        assert_eq!(Some(Rotation::IDENTITY), Rotation::all().next());
+    }
+
+    #[test]
+    fn composes_and_inverts() {
+        let point = Point3(1, 2, 3);
+        for a in Rotation::all() {
+            assert_eq!(point, a.inverse().apply(a.apply(point)));
+            assert_eq!(Rotation::IDENTITY, a.then(a.inverse()));
+            for b in Rotation::all() {
+                assert_eq!(b.apply(a.apply(point)), a.then(b).apply(point));
+            }
+        }
+    }
+
+    #[test]
+    fn aligns_point_clouds() {
+        let reference = ["0,2,0", "4,1,0", "3,3,0", "-5,0,7"]
+            .map(|point| point.parse::<Point3>().unwrap());
+        let rotation = Rotation::all().nth(13).unwrap();
+        let offset = Point3(5, -2, 10);
+        let other = reference.map(|point| rotation.inverse().apply(point - offset));
+
+        let (found_rotation, found_offset) = Rotation::align(&reference, &other, 4).unwrap();
+        for (&expected, &point) in reference.iter().zip(other.iter()) {
+            assert_eq!(expected, found_rotation.apply(point) + found_offset);
+        }
+
+        let unrelated = ["100,0,0", "0,100,0", "0,0,100", "1,1,1"].map(|p| p.parse().unwrap());
+        assert_eq!(None, Rotation::align(&reference, &unrelated, 4));
+
+        // Every side of a square has the same fingerprint, which still counts as four matches
+        let square = [
+            Point3(0, 0, 0),
+            Point3(1, 0, 0),
+            Point3(0, 1, 0),
+            Point3(1, 1, 0),
+        ];
+        let moved = square.map(|point| point + offset);
+        assert_eq!(6, super::shared_fingerprints(&square, &moved));
+        assert_eq!(
+            Some((Rotation::IDENTITY, Point3(-5, 2, -10))),
+            Rotation::align(&square, &moved, 4)
+        );
+    }
+}
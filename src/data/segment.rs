@@ -0,0 +1,318 @@
+use super::Point;
+use crate::tools::StringTools;
+use std::{cmp::Ordering, iter, str::FromStr};
+
+/// A straight line between two points, including both ends. It can go in any direction, not just
+/// along the axes or diagonals.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Segment {
+    start: Point,
+    end: Point,
+}
+
+/// Where two segments meet. See `Segment::intersection`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Intersection {
+    /// They cross (or touch) at a single point, which doesn't have to be a whole number.
+    Point(Point<f64>),
+    /// They're on top of each other, along this stretch.
+    Overlap(Segment),
+}
+
+/// Parse the `x1,y1 -> x2,y2` notation.
+impl FromStr for Segment {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_parse(" -> ")?;
+        Ok(Segment::new(start, end))
+    }
+}
+
+/// Twice the signed area of the triangle between two vectors, which is positive when `rhs` is
+/// anticlockwise of `lhs`, negative when clockwise, and zero when they're in line.
+fn cross(Point(x1, y1): Point, Point(x2, y2): Point) -> isize {
+    x1 * y2 - y1 * x2
+}
+
+fn dot(Point(x1, y1): Point, Point(x2, y2): Point) -> isize {
+    x1 * x2 + y1 * y2
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Segment {
+    pub const fn new(start: Point, end: Point) -> Self {
+        Segment { start, end }
+    }
+
+    pub fn start(self) -> Point {
+        self.start
+    }
+
+    pub fn end(self) -> Point {
+        self.end
+    }
+
+    /// The offset from the start to the end.
+    pub fn delta(self) -> Point {
+        self.end - self.start
+    }
+
+    /// The smallest whole step that goes from the start towards the end and lands exactly on the
+    /// line. For horizontal, vertical and diagonal lines, each axis is -1, 0 or 1. A segment that
+    /// starts and ends in the same place has no direction, so this is `(0, 0)`.
+    pub fn direction(self) -> Point {
+        let Point(dx, dy) = self.delta();
+        match gcd(dx, dy) {
+            0 => Point(0, 0),
+            divisor => Point(dx / divisor, dy / divisor),
+        }
+    }
+
+    /// How long the segment is, as the crow flies.
+    pub fn length(self) -> f64 {
+        let Point(dx, dy) = self.delta();
+        ((dx * dx + dy * dy) as f64).sqrt()
+    }
+
+    /// How long the segment is, if you can only move along the axes.
+    pub fn manhattan_length(self) -> isize {
+        self.start.manhattan_distance(self.end)
+    }
+
+    /// How long the segment is, if you can move diagonally as well. This is one less than the
+    /// number of points that `points` gives.
+    pub fn chebyshev_length(self) -> isize {
+        self.start.chebyshev_distance(self.end)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    pub fn is_vertical(self) -> bool {
+        self.start.0 == self.end.0
+    }
+
+    /// Whether the segment is at exactly 45 degrees (which doesn't include single points).
+    pub fn is_diagonal(self) -> bool {
+        let Point(dx, dy) = self.delta();
+        dx != 0 && dx.abs() == dy.abs()
+    }
+
+    pub fn is_axis_aligned(self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    /// Draw the segment onto a grid using Bresenham's algorithm, which gives one point per step
+    /// along the longer axis with no gaps between them. Horizontal, vertical and diagonal lines
+    /// come out exactly, and any other slope gives the closest cells to the real line.
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        let Point(dx, dy) = self.delta();
+        let step = Point(dx.signum(), dy.signum());
+        let (dx, dy) = (dx.abs(), -dy.abs());
+        let mut error = dx + dy;
+        let mut current = Some(self.start);
+
+        iter::from_fn(move || {
+            let point = current?;
+            current = if point == self.end {
+                None
+            } else {
+                let mut next = point;
+                let doubled = 2 * error;
+                if doubled >= dy {
+                    error += dy;
+                    next.0 += step.0;
+                }
+                if doubled <= dx {
+                    error += dx;
+                    next.1 += step.1;
+                }
+                Some(next)
+            };
+            Some(point)
+        })
+    }
+
+    /// Only the points that are exactly on the line, in order from the start to the end. For
+    /// steep slopes, there might only be the two ends.
+    pub fn lattice_points(self) -> impl Iterator<Item = Point> {
+        let Point(dx, dy) = self.delta();
+        let direction = self.direction();
+        let steps = gcd(dx, dy);
+        (0..=steps).map(move |idx| self.start + direction * idx)
+    }
+
+    /// Whether a point lies exactly on the segment.
+    pub fn contains(self, point: Point) -> bool {
+        // A single point has no direction, so everything would look like it's in line with it
+        if self.start == self.end {
+            return point == self.start;
+        }
+
+        let offset = point - self.start;
+        cross(self.delta(), offset) == 0
+            && (0..=dot(self.delta(), self.delta())).contains(&dot(offset, self.delta()))
+    }
+
+    /// Whether the two segments touch anywhere, including at their ends.
+    pub fn intersects(self, other: Segment) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Work out where two segments meet, if they do.
+    pub fn intersection(self, other: Segment) -> Option<Intersection> {
+        let (r, s) = (self.delta(), other.delta());
+        let offset = other.start - self.start;
+        let denominator = cross(r, s);
+
+        if denominator == 0 {
+            return self.overlap(other);
+        }
+
+        // Solve self.start + r * t = other.start + s * u, where both t and u must be between 0
+        // and 1 for the point to be on both segments.
+        let (t, u) = (cross(offset, s), cross(offset, r));
+        let within = |value: isize| {
+            if denominator > 0 {
+                (0..=denominator).contains(&value)
+            } else {
+                (denominator..=0).contains(&value)
+            }
+        };
+        if !within(t) || !within(u) {
+            return None;
+        }
+
+        let t = t as f64 / denominator as f64;
+        let Point(x, y) = self.start;
+        Some(Intersection::Point(Point(
+            x as f64 + r.0 as f64 * t,
+            y as f64 + r.1 as f64 * t,
+        )))
+    }
+
+    /// Where two parallel segments overlap, if they're on the same line.
+    fn overlap(self, other: Segment) -> Option<Intersection> {
+        let as_point = |Point(x, y): Point| Intersection::Point(Point(x as f64, y as f64));
+
+        // Points (or lines that are really points) need handling separately, since they have no
+        // direction to line things up against
+        let (line, other) = match (self.start == self.end, other.start == other.end) {
+            (true, _) => return other.contains(self.start).then(|| as_point(self.start)),
+            (false, true) => return self.contains(other.start).then(|| as_point(other.start)),
+            (false, false) => (self, other),
+        };
+        if cross(line.delta(), other.start - line.start) != 0 {
+            return None;
+        }
+
+        // Everything is on the same line now, so sort the points by how far along it they are
+        let along = |point: Point| dot(point - line.start, line.delta());
+        let (lo, hi) = if along(other.start) <= along(other.end) {
+            (other.start, other.end)
+        } else {
+            (other.end, other.start)
+        };
+        let start = if along(lo) > 0 { lo } else { line.start };
+        let end = if along(hi) < along(line.end) {
+            hi
+        } else {
+            line.end
+        };
+
+        match along(start).cmp(&along(end)) {
+            Ordering::Greater => None,
+            Ordering::Equal => Some(as_point(start)),
+            Ordering::Less => Some(Intersection::Overlap(Segment::new(start, end))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Intersection, Segment};
+    use crate::data::Point;
+
+    fn segment(s: &str) -> Segment {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn draws_every_slope() {
+        let diagonal = segment("8,0 -> 6,2");
+        assert!(diagonal.is_diagonal());
+        assert_eq!(
+            vec![Point(8, 0), Point(7, 1), Point(6, 2)],
+            diagonal.points().collect::<Vec<_>>()
+        );
+
+        let shallow = segment("0,0 -> 4,2");
+        assert_eq!(Point(2, 1), shallow.direction());
+        assert_eq!(
+            vec![
+                Point(0, 0),
+                Point(1, 1),
+                Point(2, 1),
+                Point(3, 2),
+                Point(4, 2)
+            ],
+            shallow.points().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Point(0, 0), Point(2, 1), Point(4, 2)],
+            shallow.lattice_points().collect::<Vec<_>>()
+        );
+        assert_eq!(5, shallow.chebyshev_length() + 1);
+
+        let steep = segment("3,-1 -> 1,6");
+        assert_eq!(8, steep.points().count());
+        assert_eq!(Some(Point(1, 6)), steep.points().last());
+        assert_eq!(
+            vec![Point(5, 5)],
+            segment("5,5 -> 5,5").points().collect::<Vec<_>>()
+        );
+        assert_eq!(5.0, segment("0,0 -> 3,4").length());
+    }
+
+    #[test]
+    fn finds_intersections() {
+        let cross = segment("0,0 -> 2,2").intersection(segment("0,2 -> 2,0"));
+        assert_eq!(Some(Intersection::Point(Point(1.0, 1.0))), cross);
+
+        let half = segment("0,0 -> 1,1").intersection(segment("0,1 -> 1,0"));
+        assert_eq!(Some(Intersection::Point(Point(0.5, 0.5))), half);
+
+        assert!(!segment("0,0 -> 1,1").intersects(segment("3,0 -> 2,1")));
+        assert!(!segment("0,0 -> 2,0").intersects(segment("0,1 -> 2,1")));
+        assert!(segment("0,0 -> 2,0").intersects(segment("2,0 -> 2,5")));
+
+        let overlap = segment("0,9 -> 5,9").intersection(segment("7,9 -> 2,9"));
+        assert_eq!(Some(Intersection::Overlap(segment("2,9 -> 5,9"))), overlap);
+        let touching = segment("0,0 -> 2,2").intersection(segment("4,4 -> 2,2"));
+        assert_eq!(Some(Intersection::Point(Point(2.0, 2.0))), touching);
+        assert_eq!(
+            None,
+            segment("0,0 -> 1,1").intersection(segment("2,2 -> 3,3"))
+        );
+        assert!(segment("0,0 -> 4,2").contains(Point(2, 1)));
+        assert!(!segment("0,0 -> 4,2").contains(Point(1, 0)));
+
+        let single = segment("5,5 -> 5,5");
+        assert!(single.contains(Point(5, 5)));
+        assert!(!single.contains(Point(100, -3)));
+        assert_eq!(None, segment("0,0 -> 0,0").intersection(single));
+        assert_eq!(
+            Some(Intersection::Point(Point(5.0, 5.0))),
+            segment("4,4 -> 6,6").intersection(single)
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use super::{Grid, Offsets, Point};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A grid with no edges. Only the cells that have been set are stored, and every other cell has the
+/// same background value - which can be changed at any time, for puzzles where the infinite
+/// expanse of cells all flip at once. Points are signed, so the grid can grow in any direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    background: T,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> SparseGrid<T> {
+    /// Make a new grid, where every cell has the background value.
+    pub fn new(background: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            background,
+            bounds: None,
+        }
+    }
+
+    /// Make a new sparse grid with the same contents as a dense grid, putting the dense grid's top
+    /// left corner at `(0, 0)`.
+    pub fn from_grid(grid: &Grid<T>, background: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut output = SparseGrid::new(background);
+        for (coord, value) in grid.iter() {
+            output.set(coord.into(), value.clone());
+        }
+        output
+    }
+
+    /// The value of every cell that hasn't been set.
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// Change the value of every cell that hasn't been set.
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    /// The value of a cell, which is the background value unless it has been set.
+    pub fn get(&self, point: Point) -> &T {
+        self.cells.get(&point).unwrap_or(&self.background)
+    }
+
+    /// Set the value of a cell, growing the bounding box if it needs to.
+    pub fn set(&mut self, point: Point, value: T) {
+        self.grow(point);
+        self.cells.insert(point, value);
+    }
+
+    /// The number of cells that have been set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest and largest corners (inclusive) of the box that holds every cell that has been
+    /// set, or `None` if nothing has been set yet.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Iterate over every cell that has been set (and where it is), in no particular order.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// Iterate over every point within the bounding box, after growing it by `margin` on every side,
+    /// row by row. Puzzles where the grid grows by one cell each step can use a margin of one to
+    /// visit every cell that could possibly change.
+    pub fn points(&self, margin: isize) -> impl Iterator<Item = Point> {
+        let (min, max) = match self.bounds {
+            Some((min, max)) => (min - Point(margin, margin), max + Point(margin, margin)),
+            // Make an empty range
+            None => (Point(0, 0), Point(-1, -1)),
+        };
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| Point(x, y)))
+    }
+
+    /// The neighbours of a point, where the offsets decide what counts as a neighbour. There are no
+    /// edges, so every offset gives a neighbour.
+    pub fn neighbours<'a>(
+        &self,
+        point: Point,
+        offsets: Offsets<'a>,
+    ) -> impl 'a + Iterator<Item = Point> {
+        point.neighbours(offsets)
+    }
+
+    /// The neighbours of a point, not including diagonals.
+    pub fn manhattan_neighbours(&self, point: Point) -> impl Iterator<Item = Point> {
+        point.neighbours(Offsets::MANHATTAN)
+    }
+
+    /// The neighbours of a point, including diagonals.
+    pub fn all_neighbours(&self, point: Point) -> impl Iterator<Item = Point> {
+        point.neighbours(Offsets::ALL)
+    }
+
+    fn grow(&mut self, point: Point) {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((Point(min_x, min_y), Point(max_x, max_y))) => (
+                Point(min_x.min(point.0), min_y.min(point.1)),
+                Point(max_x.max(point.0), max_y.max(point.1)),
+            ),
+        });
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+/// Writing to a cell that hasn't been set yet starts it off with the background value.
+impl<T: Clone> IndexMut<Point> for SparseGrid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.grow(point);
+        let background = &self.background;
+        self.cells
+            .entry(point)
+            .or_insert_with(|| background.clone())
+    }
+}
+
+/// Print out everything within the bounding box, one row per line, with nothing between the cells.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = match self.bounds {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        for y in min.1..=max.1 {
+            for x in min.0..=max.0 {
+                write!(f, "{}", self.get(Point(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SparseGrid;
+    use crate::data::{Grid, Point};
+
+    #[test]
+    fn grows_in_every_direction() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(None, grid.bounds());
+        grid.set(Point(-2, 1), '#');
+        grid[Point(1, -1)] = '#';
+        assert_eq!(Some((Point(-2, -1), Point(1, 1))), grid.bounds());
+        assert_eq!('.', grid[Point(100, -100)]);
+        assert_eq!("...#\n....\n#...\n", grid.to_string());
+
+        grid.set_background('#');
+        assert_eq!('#', grid[Point(100, -100)]);
+        assert_eq!(30, grid.points(1).count());
+        assert_eq!(4, grid.manhattan_neighbours(Point(0, 0)).count());
+    }
+
+    #[test]
+    fn converts_dense_grids() {
+        let dense = "12\n34\n".parse::<Grid<u8>>().unwrap();
+        let sparse = SparseGrid::from_grid(&dense, 0);
+        assert_eq!(4, sparse[Point(1, 1)]);
+        assert_eq!(0, sparse[Point(-1, 1)]);
+        assert_eq!(dense.to_string(), sparse.to_string());
+    }
+}
@@ -0,0 +1,473 @@
+//! Graphs made out of any kind of vertex, with the usual ways of getting around them, and path
+//! finding for when the steps have different costs.
+
+mod search;
+
+pub use search::{a_star, dijkstra, CostMap, Route, Tiled, Weighted};
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A graph, where the edges either go both ways (undirected) or only one way (directed). Vertices
+/// can be anything hashable, and are numbered internally in the order they were first seen, which
+/// is also the order that everything comes back out in.
+#[derive(Debug, Clone)]
+pub struct Graph<V> {
+    directed: bool,
+    vertices: Vec<V>,
+    index: HashMap<V, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<V: Eq + Hash + Clone> Graph<V> {
+    /// Make a new, empty, graph where edges only go one way.
+    pub fn directed() -> Self {
+        Graph::new(true)
+    }
+
+    /// Make a new, empty, graph where edges go both ways.
+    pub fn undirected() -> Self {
+        Graph::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            vertices: vec![],
+            index: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// Make a directed graph out of a set of `(from, to)` edges.
+    pub fn directed_from_edges(edges: impl IntoIterator<Item = (V, V)>) -> Self {
+        let mut output = Graph::directed();
+        output.extend_edges(edges);
+        output
+    }
+
+    /// Make an undirected graph out of a set of edges.
+    pub fn undirected_from_edges(edges: impl IntoIterator<Item = (V, V)>) -> Self {
+        let mut output = Graph::undirected();
+        output.extend_edges(edges);
+        output
+    }
+
+    /// Add a vertex to the graph, if it isn't there already.
+    pub fn add_vertex(&mut self, vertex: V) {
+        self.index_of_or_insert(vertex);
+    }
+
+    /// Add an edge to the graph, adding its ends as vertices if they're new. In an undirected
+    /// graph, this also adds the edge going back the other way.
+    pub fn add_edge(&mut self, from: V, to: V) {
+        let from = self.index_of_or_insert(from);
+        let to = self.index_of_or_insert(to);
+        self.edges[from].push(to);
+        if !self.directed && from != to {
+            self.edges[to].push(from);
+        }
+    }
+
+    pub fn extend_edges(&mut self, edges: impl IntoIterator<Item = (V, V)>) {
+        for (from, to) in edges {
+            self.add_edge(from, to);
+        }
+    }
+
+    fn index_of_or_insert(&mut self, vertex: V) -> usize {
+        if let Some(&idx) = self.index.get(&vertex) {
+            return idx;
+        }
+        let idx = self.vertices.len();
+        self.vertices.push(vertex.clone());
+        self.edges.push(vec![]);
+        self.index.insert(vertex, idx);
+        idx
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// The number of vertices.
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn contains(&self, vertex: &V) -> bool {
+        self.index.contains_key(vertex)
+    }
+
+    pub fn vertices(&self) -> impl '_ + Iterator<Item = &V> {
+        self.vertices.iter()
+    }
+
+    /// Every edge, as `(from, to)`. Edges in an undirected graph come out twice, once each way.
+    pub fn edges(&self) -> impl '_ + Iterator<Item = (&V, &V)> {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(move |(from, targets)| {
+                targets
+                    .iter()
+                    .map(move |&to| (&self.vertices[from], &self.vertices[to]))
+            })
+    }
+
+    /// The vertices that you can get to from a vertex in a single step. This is empty for vertices
+    /// that aren't in the graph.
+    pub fn neighbours<'a>(&'a self, vertex: &V) -> impl 'a + Iterator<Item = &'a V> {
+        self.index
+            .get(vertex)
+            .into_iter()
+            .flat_map(move |&idx| self.edges[idx].iter().map(move |&to| &self.vertices[to]))
+    }
+
+    /// Visit every vertex you can get to from `start`, nearest first.
+    pub fn bfs(&self, start: &V) -> Bfs<'_, V> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::new();
+        if let Some(&idx) = self.index.get(start) {
+            seen[idx] = true;
+            queue.push_back(idx);
+        }
+        Bfs {
+            graph: self,
+            seen,
+            queue,
+        }
+    }
+
+    /// Visit every vertex you can get to from `start`, going as deep as possible down each edge
+    /// before trying the next one.
+    pub fn dfs(&self, start: &V) -> Dfs<'_, V> {
+        Dfs {
+            graph: self,
+            seen: vec![false; self.len()],
+            stack: self.index.get(start).copied().into_iter().collect(),
+        }
+    }
+
+    /// How many steps it takes to get to every vertex that can be reached from `start`.
+    pub fn distances(&self, start: &V) -> HashMap<&V, usize> {
+        let mut output = HashMap::new();
+        for (idx, distance) in self.bfs_tree(start).distances.into_iter().enumerate() {
+            if let Some(distance) = distance {
+                output.insert(&self.vertices[idx], distance);
+            }
+        }
+        output
+    }
+
+    /// One of the routes from `from` to `to` that takes the fewest steps, including both ends.
+    pub fn shortest_path(&self, from: &V, to: &V) -> Option<Vec<&V>> {
+        let target = *self.index.get(to)?;
+        let tree = self.bfs_tree(from);
+        tree.distances[target]?;
+
+        let mut path = vec![&self.vertices[target]];
+        let mut current = target;
+        while let Some(parent) = tree.parents[current] {
+            path.push(&self.vertices[parent]);
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    fn bfs_tree(&self, start: &V) -> BfsTree {
+        let mut tree = BfsTree {
+            distances: vec![None; self.len()],
+            parents: vec![None; self.len()],
+        };
+        let start = match self.index.get(start) {
+            Some(&idx) => idx,
+            None => return tree,
+        };
+
+        tree.distances[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            let distance = tree.distances[current].unwrap_or(0) + 1;
+            for &next in &self.edges[current] {
+                if tree.distances[next].is_none() {
+                    tree.distances[next] = Some(distance);
+                    tree.parents[next] = Some(current);
+                    queue.push_back(next);
+                }
+            }
+        }
+        tree
+    }
+
+    /// Split the graph up into groups of vertices that are connected to each other. For directed
+    /// graphs, the direction of the edges is ignored.
+    pub fn components(&self) -> Vec<Vec<&V>> {
+        // Make sure that every edge can be followed backwards as well
+        let mut links = self.edges.clone();
+        if self.directed {
+            for (from, targets) in self.edges.iter().enumerate() {
+                for &to in targets {
+                    links[to].push(from);
+                }
+            }
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut output = vec![];
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![];
+            let mut stack = vec![start];
+            while let Some(current) = stack.pop() {
+                component.push(&self.vertices[current]);
+                for &next in &links[current] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+            output.push(component);
+        }
+        output
+    }
+
+    /// Put the vertices in an order where every edge goes forwards. This only works for directed
+    /// graphs without any cycles.
+    pub fn topological_sort(&self) -> eyre::Result<Vec<&V>> {
+        eyre::ensure!(self.directed, "Only directed graphs can be sorted");
+
+        let mut incoming = vec![0; self.len()];
+        for &to in self.edges.iter().flatten() {
+            incoming[to] += 1;
+        }
+
+        let mut ready = (0..self.len())
+            .filter(|&idx| incoming[idx] == 0)
+            .collect::<VecDeque<_>>();
+        let mut output = Vec::with_capacity(self.len());
+        while let Some(current) = ready.pop_front() {
+            output.push(&self.vertices[current]);
+            for &next in &self.edges[current] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        eyre::ensure!(
+            output.len() == self.len(),
+            "The graph has a cycle, so {} vertices can't be sorted",
+            self.len() - output.len()
+        );
+        Ok(output)
+    }
+
+    /// Count the routes from `from` to `to`, where `policy` decides whether the route so far is
+    /// allowed to move onto the next vertex. Routes stop as soon as they reach `to`. The policy
+    /// has to stop routes from going round in circles forever, or this never finishes.
+    pub fn count_paths(
+        &self,
+        from: &V,
+        to: &V,
+        mut policy: impl FnMut(&Path<'_, V>, &V) -> bool,
+    ) -> usize {
+        let (from, to) = match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(&to)) => (from, to),
+            _ => return 0,
+        };
+
+        let mut path = Path {
+            graph: self,
+            stack: vec![from],
+            counts: vec![0; self.len()],
+        };
+        path.counts[from] = 1;
+        self.count_paths_from(&mut path, to, &mut policy)
+    }
+
+    fn count_paths_from(
+        &self,
+        path: &mut Path<'_, V>,
+        to: usize,
+        policy: &mut impl FnMut(&Path<'_, V>, &V) -> bool,
+    ) -> usize {
+        let current = *path.stack.last().unwrap();
+        if current == to {
+            return 1;
+        }
+
+        let mut total = 0;
+        for &next in &self.edges[current] {
+            if !policy(path, &self.vertices[next]) {
+                continue;
+            }
+            path.stack.push(next);
+            path.counts[next] += 1;
+            total += self.count_paths_from(path, to, policy);
+            path.counts[next] -= 1;
+            path.stack.pop();
+        }
+        total
+    }
+}
+
+/// The distances and parents found by a breadth first search.
+struct BfsTree {
+    distances: Vec<Option<usize>>,
+    parents: Vec<Option<usize>>,
+}
+
+/// The route taken so far, for deciding where a route can go next. See `Graph::count_paths`.
+#[derive(Debug)]
+pub struct Path<'a, V> {
+    graph: &'a Graph<V>,
+    stack: Vec<usize>,
+    counts: Vec<usize>,
+}
+
+impl<'a, V: Eq + Hash> Path<'a, V> {
+    /// How many times the route has been through a vertex.
+    pub fn count(&self, vertex: &V) -> usize {
+        self.graph
+            .index
+            .get(vertex)
+            .map_or(0, |&idx| self.counts[idx])
+    }
+
+    /// The vertices on the route so far, in order.
+    pub fn vertices(&self) -> impl '_ + Iterator<Item = &V> {
+        self.stack.iter().map(move |&idx| &self.graph.vertices[idx])
+    }
+
+    /// The number of vertices on the route so far.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+}
+
+/// See `Graph::bfs`.
+#[derive(Debug, Clone)]
+pub struct Bfs<'a, V> {
+    graph: &'a Graph<V>,
+    seen: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl<'a, V> Iterator for Bfs<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.queue.pop_front()?;
+        for &next in &self.graph.edges[current] {
+            if !self.seen[next] {
+                self.seen[next] = true;
+                self.queue.push_back(next);
+            }
+        }
+        Some(&self.graph.vertices[current])
+    }
+}
+
+/// See `Graph::dfs`.
+#[derive(Debug, Clone)]
+pub struct Dfs<'a, V> {
+    graph: &'a Graph<V>,
+    seen: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl<'a, V> Iterator for Dfs<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current = self.stack.pop()?;
+            if self.seen[current] {
+                continue;
+            }
+            self.seen[current] = true;
+            // Push in reverse, so the first edge is the first one explored
+            self.stack.extend(
+                self.graph.edges[current]
+                    .iter()
+                    .rev()
+                    .filter(|&&next| !self.seen[next]),
+            );
+            return Some(&self.graph.vertices[current]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Graph;
+
+    #[test]
+    fn walks_graphs() {
+        let graph =
+            Graph::undirected_from_edges(vec![(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 7)]);
+        assert_eq!(vec![&1, &2, &3, &4, &5], graph.bfs(&1).collect::<Vec<_>>());
+        assert_eq!(vec![&1, &2, &4, &3, &5], graph.dfs(&1).collect::<Vec<_>>());
+        assert_eq!(Some(vec![&1, &2, &4, &5]), graph.shortest_path(&1, &5));
+        assert_eq!(None, graph.shortest_path(&1, &7));
+        assert_eq!(Some(&3), graph.distances(&1).get(&5));
+        assert_eq!(2, graph.components().len());
+        assert_eq!(0, graph.bfs(&100).count());
+    }
+
+    #[test]
+    fn sorts_directed_graphs() {
+        let mut graph = Graph::directed_from_edges(vec![("shirt", "tie"), ("tie", "jacket")]);
+        graph.add_edge("trousers", "shoes");
+        graph.add_edge("socks", "shoes");
+        graph.add_edge("trousers", "jacket");
+        graph.add_vertex("hat");
+        let order = graph.topological_sort().unwrap();
+        let position = |item| order.iter().position(|&&other| other == item).unwrap();
+        for (from, to) in graph.edges() {
+            assert!(position(*from) < position(*to));
+        }
+        assert_eq!(2, graph.components().len());
+
+        graph.add_edge("jacket", "shirt");
+        let error = graph.topological_sort().unwrap_err();
+        assert_eq!(
+            "The graph has a cycle, so 3 vertices can't be sorted",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn counts_paths() {
+        // A grid of a x b squares has (a + b choose a) routes across it, going right and down
+        let graph = Graph::directed_from_edges(
+            (0..4)
+                .flat_map(|x| {
+                    (0..4).flat_map(move |y| vec![((x, y), (x + 1, y)), ((x, y), (x, y + 1))])
+                })
+                .filter(|&(_, (x, y))| x <= 3 && y <= 3),
+        );
+        assert_eq!(20, graph.count_paths(&(0, 0), &(3, 3), |_, _| true));
+
+        let cycle = Graph::undirected_from_edges(vec![('a', 'b'), ('b', 'c'), ('c', 'a')]);
+        let once = cycle.count_paths(&'a', &'c', |path, next| path.count(next) == 0);
+        assert_eq!(2, once);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::data::{Coordinate, Grid};
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+/// The cheapest way to get somewhere, found by `dijkstra` or `a_star`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Route<N> {
+    /// The total cost of every step along the way.
+    pub cost: u64,
+    /// Every node on the way, including the start and the goal.
+    pub path: Vec<N>,
+}
+
+/// Find the cheapest route from `start` to any node that `is_goal` likes, where `successors` gives
+/// the nodes that are one step away from a node and what it costs to step to each of them.
+pub fn dijkstra<N, S>(
+    start: N,
+    successors: impl FnMut(&N) -> S,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N>>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = (N, u64)>,
+{
+    a_star(start, successors, |_| 0, is_goal)
+}
+
+/// The same as `dijkstra`, but with a guess at how much it'll cost to get from a node to the goal,
+/// so that the search heads the right way first. The guess must never be more than the real cost,
+/// or the route that comes out might not be the cheapest one.
+pub fn a_star<N, S>(
+    start: N,
+    mut successors: impl FnMut(&N) -> S,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N>>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = (N, u64)>,
+{
+    // Every node gets a number the first time it's seen, so the rest of the bookkeeping can be done
+    // with plain old vectors
+    let mut nodes = vec![start.clone()];
+    let mut index = HashMap::from([(start, 0)]);
+    let mut best = vec![0];
+    let mut parents: Vec<Option<usize>> = vec![None];
+
+    // Ordered by estimated total cost, and then by cost so far
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&nodes[0]), 0, 0))]);
+    while let Some(Reverse((_, cost, current))) = queue.pop() {
+        if cost > best[current] {
+            // There's already been a cheaper way here
+            continue;
+        }
+        if is_goal(&nodes[current]) {
+            let mut path = vec![nodes[current].clone()];
+            let mut idx = current;
+            while let Some(parent) = parents[idx] {
+                path.push(nodes[parent].clone());
+                idx = parent;
+            }
+            path.reverse();
+            return Some(Route { cost, path });
+        }
+
+        for (next, step) in successors(&nodes[current]) {
+            let next_cost = cost + step;
+            let next_idx = match index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let idx = *entry.get();
+                    if next_cost >= best[idx] {
+                        continue;
+                    }
+                    best[idx] = next_cost;
+                    parents[idx] = Some(current);
+                    idx
+                }
+                Entry::Vacant(entry) => {
+                    let idx = nodes.len();
+                    nodes.push(entry.key().clone());
+                    entry.insert(idx);
+                    best.push(next_cost);
+                    parents.push(Some(current));
+                    idx
+                }
+            };
+            let estimate = next_cost + heuristic(&nodes[next_idx]);
+            queue.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+/// Anything shaped like a grid, where stepping onto a cell costs something. Moves only go up, down,
+/// left and right, and the cell you start on is free.
+pub trait CostMap {
+    /// One past the bottom right corner, like `Grid::max_point`.
+    fn max_point(&self) -> Coordinate;
+
+    /// How much it costs to step onto a cell, or `None` if you can't.
+    fn entry_cost(&self, coord: Coordinate) -> Option<u64>;
+
+    /// Find the cheapest route from one cell to another.
+    fn cheapest_path(&self, start: Coordinate, goal: Coordinate) -> Option<Route<Coordinate>>
+    where
+        Self: Sized,
+    {
+        self.cheapest_path_with(start, goal, |_| 0)
+    }
+
+    /// Find the cheapest route from one cell to another, using a guess of the cost from each cell to
+    /// the goal to speed things up (see `a_star`).
+    fn cheapest_path_with(
+        &self,
+        start: Coordinate,
+        goal: Coordinate,
+        heuristic: impl FnMut(&Coordinate) -> u64,
+    ) -> Option<Route<Coordinate>>
+    where
+        Self: Sized,
+    {
+        let max_point = self.max_point();
+        a_star(
+            start,
+            |&coord| {
+                coord
+                    .manhattan_neighbours(max_point)
+                    .filter_map(|next| Some((next, self.entry_cost(next)?)))
+            },
+            heuristic,
+            |&coord| coord == goal,
+        )
+    }
+}
+
+/// Each cell's value is how much it costs to step onto it.
+impl<T: Copy + Into<u64>> CostMap for Grid<T> {
+    fn max_point(&self) -> Coordinate {
+        Grid::max_point(self)
+    }
+
+    fn entry_cost(&self, coord: Coordinate) -> Option<u64> {
+        self.get(coord).map(|&value| value.into())
+    }
+}
+
+/// A grid where a function works out the cost of stepping onto each cell from its value. The
+/// function can return `None` for walls.
+#[derive(Debug, Clone)]
+pub struct Weighted<'a, T, F> {
+    grid: &'a Grid<T>,
+    cost: F,
+}
+
+impl<'a, T, F: Fn(&T) -> Option<u64>> Weighted<'a, T, F> {
+    pub fn new(grid: &'a Grid<T>, cost: F) -> Self {
+        Weighted { grid, cost }
+    }
+}
+
+impl<'a, T, F: Fn(&T) -> Option<u64>> CostMap for Weighted<'a, T, F> {
+    fn max_point(&self) -> Coordinate {
+        self.grid.max_point()
+    }
+
+    fn entry_cost(&self, coord: Coordinate) -> Option<u64> {
+        self.grid.get(coord).and_then(&self.cost)
+    }
+}
+
+/// A grid repeated `across` times to the right and `down` times downwards, without actually
+/// making the bigger grid. The cost of a cell depends on its value and which tile it's in, where
+/// the top left tile is `(0, 0)`.
+#[derive(Debug, Clone)]
+pub struct Tiled<'a, T, F> {
+    grid: &'a Grid<T>,
+    across: usize,
+    down: usize,
+    cost: F,
+}
+
+impl<'a, T, F: Fn(&T, Coordinate) -> Option<u64>> Tiled<'a, T, F> {
+    pub fn new(grid: &'a Grid<T>, across: usize, down: usize, cost: F) -> Self {
+        Tiled {
+            grid,
+            across,
+            down,
+            cost,
+        }
+    }
+}
+
+impl<'a, T, F: Fn(&T, Coordinate) -> Option<u64>> CostMap for Tiled<'a, T, F> {
+    fn max_point(&self) -> Coordinate {
+        Coordinate(
+            self.grid.width() * self.across,
+            self.grid.height() * self.down,
+        )
+    }
+
+    fn entry_cost(&self, Coordinate(x, y): Coordinate) -> Option<u64> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        if width == 0 || height == 0 || x >= width * self.across || y >= height * self.down {
+            return None;
+        }
+        let tile = Coordinate(x / width, y / height);
+        (self.cost)(&self.grid[Coordinate(x % width, y % height)], tile)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CostMap, Tiled, Weighted};
+    use crate::{
+        data::{Coordinate, Grid, Point},
+        tools::StringTools,
+    };
+
+    const CAVE: &str = r"
+        1163751742
+        1381373672
+        2136511328
+        3694931569
+        7463417111
+        1319128137
+        1359912421
+        3125421639
+        1293138521
+        2311944581
+    ";
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let grid = CAVE.dedent().parse::<Grid<u8>>().unwrap();
+        let goal = Coordinate(9, 9);
+        let route = grid.cheapest_path(Coordinate(0, 0), goal).unwrap();
+        assert_eq!(40, route.cost);
+        assert_eq!(Some(&goal), route.path.last());
+        let path_cost = route.path[1..]
+            .iter()
+            .map(|&coord| grid[coord] as u64)
+            .sum::<u64>();
+        assert_eq!(40, path_cost);
+
+        let manhattan =
+            |coord: &Coordinate| Point::from(*coord).manhattan_distance(goal.into()) as u64;
+        let guided = grid
+            .cheapest_path_with(Coordinate(0, 0), goal, manhattan)
+            .unwrap();
+        assert_eq!(40, guided.cost);
+
+        // Walls get in the way
+        let walls = Weighted::new(&grid, |&risk| (risk < 9).then_some(1));
+        assert_eq!(
+            None,
+            walls.cheapest_path(Coordinate(0, 0), Coordinate(2, 3))
+        );
+        assert_eq!(
+            18,
+            walls.cheapest_path(Coordinate(0, 0), goal).unwrap().cost
+        );
+    }
+
+    #[test]
+    fn expands_tiles() {
+        let grid = CAVE.dedent().parse::<Grid<u8>>().unwrap();
+        let tiled = Tiled::new(&grid, 5, 5, |&risk, Coordinate(x, y)| {
+            Some((risk as u64 + x as u64 + y as u64 - 1) % 9 + 1)
+        });
+        assert_eq!(Coordinate(50, 50), tiled.max_point());
+        assert_eq!(Some(7), tiled.entry_cost(Coordinate(48, 49)));
+        let route = tiled
+            .cheapest_path(Coordinate(0, 0), Coordinate(49, 49))
+            .unwrap();
+        assert_eq!(315, route.cost);
+    }
+}
@@ -3,6 +3,10 @@
 //! shorter.
 
 pub mod data;
+pub mod graph;
+pub mod ocr;
 pub mod parsing;
+pub mod render;
+pub mod simulation;
 pub mod util;
 pub mod tools;
\ No newline at end of file
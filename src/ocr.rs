@@ -0,0 +1,294 @@
+//! Some puzzles give their answers as a picture of capital letters, drawn with lit cells. This
+//! reads them back out as a string, so the answer can be printed (and tested) like any other.
+
+use crate::{
+    data::{Coordinate, Grid, Point},
+    render,
+};
+
+/// One of the block letter alphabets that turn up in answers. Every letter in a font takes up the
+/// same size box, with the letters spaced out at an even pitch.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Font {
+    height: usize,
+    pitch: usize,
+    /// Each letter, with the rows of its picture separated by spaces.
+    glyphs: &'static [(char, &'static str)],
+}
+
+impl Font {
+    /// The small letters, four cells wide and six tall, with one empty column between them.
+    pub const SMALL: Font = Font {
+        height: 6,
+        pitch: 5,
+        glyphs: &[
+            ('A', ".##. #..# #..# #### #..# #..#"),
+            ('B', "###. #..# ###. #..# #..# ###."),
+            ('C', ".##. #..# #... #... #..# .##."),
+            ('E', "#### #... ###. #... #... ####"),
+            ('F', "#### #... ###. #... #... #..."),
+            ('G', ".##. #..# #... #.## #..# .###"),
+            ('H', "#..# #..# #### #..# #..# #..#"),
+            ('I', ".### ..#. ..#. ..#. ..#. .###"),
+            ('J', "..## ...# ...# ...# #..# .##."),
+            ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+            ('L', "#... #... #... #... #... ####"),
+            ('O', ".##. #..# #..# #..# #..# .##."),
+            ('P', "###. #..# #..# ###. #... #..."),
+            ('R', "###. #..# #..# ###. #.#. #..#"),
+            ('S', ".### #... #... .##. ...# ###."),
+            ('U', "#..# #..# #..# #..# #..# .##."),
+            // Y is the odd one out, and spills over into the gap between letters
+            ('Y', "#...# #...# .#.#. ..#.. ..#.. ..#.."),
+            ('Z', "#### ...# ..#. .#.. #... ####"),
+        ],
+    };
+
+    /// The large letters, six cells wide and ten tall, with two empty columns between them.
+    pub const LARGE: Font = Font {
+        height: 10,
+        pitch: 8,
+        glyphs: &[
+            (
+                'A',
+                "..##.. .#..#. #....# #....# #....# ###### #....# #....# #....# #....#",
+            ),
+            (
+                'B',
+                "#####. #....# #....# #....# #####. #....# #....# #....# #....# #####.",
+            ),
+            (
+                'C',
+                ".####. #....# #..... #..... #..... #..... #..... #..... #....# .####.",
+            ),
+            (
+                'E',
+                "###### #..... #..... #..... #####. #..... #..... #..... #..... ######",
+            ),
+            (
+                'F',
+                "###### #..... #..... #..... #####. #..... #..... #..... #..... #.....",
+            ),
+            (
+                'G',
+                ".####. #....# #..... #..... #..... #..### #....# #....# #...## .###.#",
+            ),
+            (
+                'H',
+                "#....# #....# #....# #....# ###### #....# #....# #....# #....# #....#",
+            ),
+            (
+                'J',
+                "...### ....#. ....#. ....#. ....#. ....#. ....#. #...#. #...#. .###..",
+            ),
+            (
+                'K',
+                "#....# #...#. #..#.. #.#... ##.... ##.... #.#... #..#.. #...#. #....#",
+            ),
+            (
+                'L',
+                "#..... #..... #..... #..... #..... #..... #..... #..... #..... ######",
+            ),
+            (
+                'N',
+                "#....# ##...# ##...# #.#..# #.#..# #..#.# #..#.# #...## #...## #....#",
+            ),
+            (
+                'P',
+                "#####. #....# #....# #....# #####. #..... #..... #..... #..... #.....",
+            ),
+            (
+                'R',
+                "#####. #....# #....# #....# #####. #..#.. #...#. #...#. #....# #....#",
+            ),
+            (
+                'X',
+                "#....# #....# .#..#. .#..#. ..##.. ..##.. .#..#. .#..#. #....# #....#",
+            ),
+            (
+                'Z',
+                "###### .....# .....# ....#. ...#.. ..#... .#.... #..... #..... ######",
+            ),
+        ],
+    };
+
+    /// Read the letters out of a grid, where `true` is a lit cell. The letters can be anywhere in
+    /// the grid, as long as they're all on one line.
+    pub fn read(&self, grid: &Grid<bool>) -> eyre::Result<String> {
+        let (min, max) =
+            lit_bounds(grid).ok_or_else(|| eyre::format_err!("There's nothing to read"))?;
+        let height = (max.1 - min.1 + 1) as usize;
+        eyre::ensure!(
+            height == self.height,
+            "The letters are {} cells tall, but this font's letters are {} cells tall",
+            height,
+            self.height
+        );
+
+        // Most letters light up their leftmost column, but not all of them (I'm looking at you, I),
+        // so the first letter might start a little before the first lit cell.
+        let mut first_attempt = None;
+        for shift in 0..self.pitch as isize {
+            let start = Point(min.0 - shift, min.1);
+            let letters = (0..)
+                .map(|idx| start + Point((idx * self.pitch) as isize, 0))
+                .take_while(|corner| corner.0 <= max.0)
+                .map(|corner| (corner, self.recognise(grid, corner)))
+                .collect::<Vec<_>>();
+
+            if letters.iter().all(|(_, letter)| letter.is_some()) {
+                return Ok(letters
+                    .into_iter()
+                    .filter_map(|(_, letter)| letter)
+                    .collect());
+            }
+            first_attempt.get_or_insert(letters);
+        }
+
+        // Report how the most obvious alignment went wrong
+        let letters = first_attempt.unwrap_or_default();
+        let partial = letters
+            .iter()
+            .map(|(_, letter)| letter.unwrap_or('?'))
+            .collect::<String>();
+        let unknown = letters
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, letter))| letter.is_none())
+            .map(|(idx, (Point(x, y), _))| format!("letter {} at ({}, {})", idx + 1, x, y))
+            .collect::<Vec<_>>();
+        eyre::bail!(
+            "Couldn't recognise {} (read so far: {:?})",
+            unknown.join(", "),
+            partial
+        )
+    }
+
+    /// Read the letters out of a set of lit points.
+    pub fn read_points(&self, points: impl IntoIterator<Item = Point>) -> eyre::Result<String> {
+        self.read(&render::plot(points))
+    }
+
+    /// Work out which letter has its top left corner at `corner`, if any.
+    fn recognise(&self, grid: &Grid<bool>, corner: Point) -> Option<char> {
+        let is_lit = |point: Point| {
+            Coordinate::try_from(point)
+                .ok()
+                .and_then(|coord| grid.get(coord))
+                .copied()
+                .unwrap_or(false)
+        };
+
+        self.glyphs.iter().find_map(|&(letter, rows)| {
+            let matches = rows.split(' ').enumerate().all(|(y, row)| {
+                (0..self.pitch).all(|x| {
+                    let expected = row.as_bytes().get(x) == Some(&b'#');
+                    is_lit(corner + Point(x as isize, y as isize)) == expected
+                })
+            });
+            matches.then_some(letter)
+        })
+    }
+}
+
+/// Read the letters out of a grid, picking whichever font fits their height.
+pub fn read(grid: &Grid<bool>) -> eyre::Result<String> {
+    let font = match lit_bounds(grid) {
+        Some((min, max)) if max.1 - min.1 + 1 == Font::LARGE.height as isize => Font::LARGE,
+        _ => Font::SMALL,
+    };
+    font.read(grid)
+}
+
+/// Read the letters out of a set of lit points, picking whichever font fits their height.
+pub fn read_points(points: impl IntoIterator<Item = Point>) -> eyre::Result<String> {
+    read(&render::plot(points))
+}
+
+/// The corners of the smallest box around all of the lit cells.
+fn lit_bounds(grid: &Grid<bool>) -> Option<(Point, Point)> {
+    grid.iter()
+        .filter(|&(_, &lit)| lit)
+        .map(|(coord, _)| Point::from(coord))
+        .fold(None, |bounds, point| {
+            Some(match bounds {
+                None => (point, point),
+                Some((Point(min_x, min_y), Point(max_x, max_y))) => (
+                    Point(min_x.min(point.0), min_y.min(point.1)),
+                    Point(max_x.max(point.0), max_y.max(point.1)),
+                ),
+            })
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::Font;
+    use crate::{data::Grid, tools::StringTools};
+
+    fn parse(picture: &str) -> Grid<bool> {
+        picture
+            .dedent()
+            .parse::<Grid<char>>()
+            .unwrap()
+            .map(|&cell| cell == '#')
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let picture = parse(
+            r"
+            .....................
+            ..###.#..#.#...####..
+            ...#..#..#.#...##..#.
+            ...#..####..#.#.###..
+            ...#..#..#...#..#..#.
+            ...#..#..#...#..#..#.
+            ..###.#..#...#..###..
+            ",
+        );
+        assert_eq!("IHYB", super::read(&picture).unwrap());
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let picture = parse(
+            r"
+            #....#..######
+            ##...#.......#
+            ##...#.......#
+            #.#..#......#.
+            #.#..#.....#..
+            #..#.#....#...
+            #..#.#...#....
+            #...##..#.....
+            #...##..#.....
+            #....#..######
+            ",
+        );
+        let points = picture
+            .iter()
+            .filter(|&(_, &lit)| lit)
+            .map(|(coord, _)| coord.into());
+        assert_eq!("NZ", Font::LARGE.read_points(points).unwrap());
+    }
+
+    #[test]
+    fn points_out_unknown_letters() {
+        let picture = parse(
+            r"
+            ####.#####
+            #..#.#...#
+            #..#.#...#
+            #..#.#...#
+            #..#.#...#
+            ####.#####
+            ",
+        );
+        let error = super::read(&picture).unwrap_err().to_string();
+        assert_eq!(
+            r#"Couldn't recognise letter 1 at (0, 0), letter 2 at (5, 0) (read so far: "??")"#,
+            error
+        );
+    }
+}
@@ -0,0 +1,405 @@
+//! A tiny set of parser combinators, for the inputs that are too nested or recursive to get through
+//! `split_parse` and friends. A parser is anything that takes the remaining input and either hands
+//! back a value plus whatever input it didn't consume, or a `Failure` saying what it expected to see.
+//! Plain functions with the right signature are parsers too, which is how recursive formats are
+//! handled - write a `fn` that refers to itself somewhere inside the combinators it builds.
+//!
+//! Once you've built a parser, run it with `parse_all` to get a `ParseError` (which converts into an
+//! `eyre::Report`) with the line and column that things went wrong at.
+
+use std::{borrow::Cow, error::Error, fmt, str::FromStr};
+
+/// The outcome of running a parser - either the parsed value and the rest of the input, or a
+/// description of what went wrong.
+pub type ParseResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// A parser failed. This keeps hold of the remaining input at the point of failure, so that the
+/// position can be worked out once we know what the whole input was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    /// The input that was left when the parser gave up.
+    pub rest: &'a str,
+    /// A human readable description of what the parser wanted to see.
+    pub expected: Cow<'static, str>,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new(rest: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Failure {
+            rest,
+            expected: expected.into(),
+        }
+    }
+}
+
+/// Something that can parse a `T` off the front of a string.
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> ParseResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Match an exact string.
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => Err(Failure::new(input, format!("{:?}", expected))),
+    }
+}
+
+/// Match a single character, as long as it passes the predicate. The description is used for the
+/// error message if it doesn't.
+pub fn char_where<'a>(
+    description: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(ch) if predicate(ch) => Ok((ch, &input[ch.len_utf8()..])),
+        _ => Err(Failure::new(input, description)),
+    }
+}
+
+/// Match as many characters as possible that pass the predicate. Will happily match nothing at all,
+/// so wrap it in `non_empty` if that's not what you want.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input
+            .char_indices()
+            .find(|&(_, ch)| !predicate(ch))
+            .map(|(idx, _)| idx)
+            .unwrap_or(input.len());
+        Ok(input.split_at(end))
+    }
+}
+
+/// Require that a string parser matched at least one character.
+pub fn non_empty<'a>(
+    description: &'static str,
+    inner: impl Parser<'a, &'a str>,
+) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match inner.parse(input)? {
+        ("", _) => Err(Failure::new(input, description)),
+        output => Ok(output),
+    }
+}
+
+/// Parse a (possibly negative) decimal integer of any type that can be parsed from a string.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let unsigned = input
+            .strip_prefix(|ch| ch == '-' || ch == '+')
+            .unwrap_or(input);
+        let (digits, rest) = take_while(|ch| ch.is_ascii_digit()).parse(unsigned)?;
+        if digits.is_empty() {
+            return Err(Failure::new(input, "an integer"));
+        }
+
+        let number = &input[..input.len() - rest.len()];
+        match number.parse() {
+            Ok(value) => Ok((value, rest)),
+            Err(_) => Err(Failure::new(
+                input,
+                format!("an integer that fits in a {}", std::any::type_name::<T>()),
+            )),
+        }
+    }
+}
+
+/// Only succeed if there's no input left.
+pub fn eof<'a>() -> impl Parser<'a, ()> {
+    move |input: &'a str| {
+        if input.is_empty() {
+            Ok(((), input))
+        } else {
+            Err(Failure::new(input, "the end of the input"))
+        }
+    }
+}
+
+/// Run a parser and then transform its output.
+pub fn map<'a, A, B>(inner: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |input: &'a str| {
+        let (value, rest) = inner.parse(input)?;
+        Ok((f(value), rest))
+    }
+}
+
+/// Run a parser and then transform its output with a function that might fail. The failure is
+/// reported at the start of whatever the inner parser matched.
+pub fn map_res<'a, A, B, E: fmt::Display>(
+    inner: impl Parser<'a, A>,
+    f: impl Fn(A) -> Result<B, E>,
+) -> impl Parser<'a, B> {
+    move |input: &'a str| {
+        let (value, rest) = inner.parse(input)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(e) => Err(Failure::new(input, e.to_string())),
+        }
+    }
+}
+
+/// Try a parser, but carry on without consuming anything if it fails.
+pub fn opt<'a, T>(inner: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match inner.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// Run two parsers, one after the other, and keep both outputs.
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = first.parse(input)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// Run two parsers, one after the other, and keep only the second output.
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// Run two parsers, one after the other, and keep only the first output.
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// Run three parsers, one after the other, and keep only the middle output. Good for brackets.
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    inner: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(inner, close))
+}
+
+/// Run a parser as many times as it will succeed, collecting the outputs. This can match zero times.
+pub fn many<'a, T>(inner: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut output = Vec::new();
+        while let Ok((value, rest)) = inner.parse(input) {
+            // A parser that doesn't consume anything would match forever
+            if rest.len() == input.len() {
+                break;
+            }
+            output.push(value);
+            input = rest;
+        }
+
+        Ok((output, input))
+    }
+}
+
+/// Parse one or more items with a separator between each of them. Every separator has to be
+/// followed by another item, so trim off any trailing separators first.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, mut input) = item.parse(input)?;
+        let mut output = vec![first];
+        loop {
+            let after_separator = match separator.parse(input) {
+                Ok((_, rest)) => rest,
+                Err(_) => break,
+            };
+            let (value, rest) = item.parse(after_separator)?;
+            output.push(value);
+            input = rest;
+        }
+
+        Ok((output, input))
+    }
+}
+
+/// A tuple of parsers that all produce the same type, to be tried in order. See `alt`.
+pub trait Alternatives<'a, T> {
+    fn parse_first(&self, input: &'a str) -> ParseResult<'a, T>;
+}
+
+/// When every alternative fails, we report the one that made it the furthest through the input,
+/// since that's usually the one that the input was meant to be.
+fn furthest<'a>(lhs: Failure<'a>, rhs: Failure<'a>) -> Failure<'a> {
+    if lhs.rest.len() < rhs.rest.len() {
+        lhs
+    } else if rhs.rest.len() < lhs.rest.len() {
+        rhs
+    } else {
+        Failure::new(lhs.rest, format!("{} or {}", lhs.expected, rhs.expected))
+    }
+}
+
+macro_rules! impl_alternatives {
+    ($first:ident $first_idx:tt $(, $name:ident $idx:tt)+) => {
+        impl<'a, T, $first $(, $name)+> Alternatives<'a, T> for ($first $(, $name)+)
+        where
+            $first: Parser<'a, T>,
+            $($name: Parser<'a, T>,)+
+        {
+            fn parse_first(&self, input: &'a str) -> ParseResult<'a, T> {
+                #[allow(unused_mut)]
+                let mut failure = match self.$first_idx.parse(input) {
+                    Ok(output) => return Ok(output),
+                    Err(failure) => failure,
+                };
+                $(
+                    failure = match self.$idx.parse(input) {
+                        Ok(output) => return Ok(output),
+                        Err(other) => furthest(failure, other),
+                    };
+                )+
+                Err(failure)
+            }
+        }
+    };
+}
+
+impl_alternatives!(A 0, B 1);
+impl_alternatives!(A 0, B 1, C 2);
+impl_alternatives!(A 0, B 1, C 2, D 3);
+impl_alternatives!(A 0, B 1, C 2, D 3, E 4);
+impl_alternatives!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Try each parser in a tuple in turn, returning the output of the first one that succeeds.
+pub fn alt<'a, T>(alternatives: impl Alternatives<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| alternatives.parse_first(input)
+}
+
+/// Something went wrong parsing an input, and this is where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line that the parser failed on, starting at 1.
+    pub line: usize,
+    /// The column (in characters) that the parser failed on, starting at 1.
+    pub column: usize,
+    /// What the parser wanted to see at that point.
+    pub expected: String,
+    /// A short snippet of what it actually found.
+    pub found: String,
+}
+
+impl ParseError {
+    fn new(input: &str, failure: Failure<'_>) -> Self {
+        let consumed = &input[..input.len() - failure.rest.len()];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        let column = consumed[line_start..].chars().count() + 1;
+
+        let found = match failure.rest.lines().next() {
+            None | Some("") => "the end of the line".to_string(),
+            Some(snippet) => format!("{:?}", snippet.chars().take(10).collect::<String>()),
+        };
+
+        ParseError {
+            line,
+            column,
+            expected: failure.expected.into_owned(),
+            found,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected {} at line {}, column {}, but found {}",
+            self.expected, self.line, self.column, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Run a parser over an entire input, failing if it doesn't consume all of it.
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, input: &'a str) -> Result<T, ParseError> {
+    terminated(parser, eof())
+        .parse(input)
+        .map(|(value, _rest)| value)
+        .map_err(|failure| ParseError::new(input, failure))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Snailfish {
+        Number(u32),
+        Pair(Box<Snailfish>, Box<Snailfish>),
+    }
+
+    fn snailfish(input: &str) -> ParseResult<'_, Snailfish> {
+        let number = map(integer(), Snailfish::Number);
+        let pair = map(
+            delimited(
+                tag("["),
+                pair(snailfish, preceded(tag(","), snailfish)),
+                tag("]"),
+            ),
+            |(lhs, rhs)| Snailfish::Pair(Box::new(lhs), Box::new(rhs)),
+        );
+
+        alt((number, pair)).parse(input)
+    }
+
+    fn range(input: &str) -> ParseResult<'_, (i32, i32)> {
+        pair(terminated(integer(), tag("..")), integer()).parse(input)
+    }
+
+    #[test]
+    fn parses_recursive_input() {
+        let output = parse_all(snailfish, "[[1,2],3]").unwrap();
+        assert_eq!(
+            Snailfish::Pair(
+                Box::new(Snailfish::Pair(
+                    Box::new(Snailfish::Number(1)),
+                    Box::new(Snailfish::Number(2))
+                )),
+                Box::new(Snailfish::Number(3))
+            ),
+            output
+        );
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let axis = preceded(
+            pair(char_where("an axis", |ch| "xyz".contains(ch)), tag("=")),
+            range,
+        );
+        let output = parse_all(separated(axis, tag(",")), "x=10..12,y=-5..3").unwrap();
+        assert_eq!(vec![(10, 12), (-5, 3)], output);
+    }
+
+    #[test]
+    fn reports_error_position() {
+        let error = parse_all(separated(snailfish, tag("\n")), "[1,2]\n[3,x]").unwrap_err();
+        assert_eq!(2, error.line);
+        assert_eq!(4, error.column);
+        assert_eq!("an integer or \"[\"", error.expected);
+
+        let error = parse_all(integer::<u8>(), "300").unwrap_err();
+        assert_eq!(1, error.column);
+        assert_eq!("an integer that fits in a u8", error.expected);
+    }
+}
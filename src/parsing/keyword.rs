@@ -0,0 +1,126 @@
+/// A fixed set of keywords, each of which parses into a value. When the input doesn't match any of
+/// them, the error lists all of the keywords that would have worked, and points out the closest one
+/// if it looks like a typo.
+#[derive(Debug, Copy, Clone)]
+pub struct Keywords<'a, T> {
+    options: &'a [(&'a str, T)],
+    ignore_case: bool,
+}
+
+impl<'a, T: Clone> Keywords<'a, T> {
+    /// Set up a new set of keywords. These match exactly, unless `ignore_case` is used.
+    pub const fn new(options: &'a [(&'a str, T)]) -> Self {
+        Keywords {
+            options,
+            ignore_case: false,
+        }
+    }
+
+    /// Match keywords regardless of upper or lower case.
+    pub const fn ignore_case(self) -> Self {
+        Keywords {
+            ignore_case: true,
+            ..self
+        }
+    }
+
+    /// Find the value for a keyword.
+    pub fn parse(&self, input: &str) -> eyre::Result<T> {
+        let found = self.options.iter().find(|(keyword, _)| {
+            if self.ignore_case {
+                keyword.eq_ignore_ascii_case(input)
+            } else {
+                *keyword == input
+            }
+        });
+        if let Some((_, value)) = found {
+            return Ok(value.clone());
+        }
+
+        let valid = self
+            .options
+            .iter()
+            .map(|(keyword, _)| format!("{:?}", keyword))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // Only suggest things that are a plausible typo, rather than just the least bad option
+        let suggestion = self
+            .options
+            .iter()
+            .map(|(keyword, _)| (edit_distance(keyword, input, self.ignore_case), keyword))
+            .filter(|&(distance, keyword)| distance <= (keyword.chars().count() / 2).max(1))
+            .min_by_key(|&(distance, _)| distance);
+
+        match suggestion {
+            Some((_, keyword)) => eyre::bail!(
+                "Invalid keyword {:?} (did you mean {:?}?), expected one of {}",
+                input,
+                keyword,
+                valid
+            ),
+            None => eyre::bail!("Invalid keyword {:?}, expected one of {}", input, valid),
+        }
+    }
+}
+
+/// The number of single character insertions, deletions or substitutions needed to turn one string
+/// into the other.
+fn edit_distance(lhs: &str, rhs: &str, ignore_case: bool) -> usize {
+    let normalise = |ch: char| {
+        if ignore_case {
+            ch.to_ascii_lowercase()
+        } else {
+            ch
+        }
+    };
+    let rhs = rhs.chars().map(normalise).collect::<Vec<_>>();
+
+    // Only keep the previous row of the table around, since that's all each new row looks at
+    let mut previous = (0..=rhs.len()).collect::<Vec<_>>();
+    for (i, lhs_ch) in lhs.chars().map(normalise).enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &rhs_ch) in rhs.iter().enumerate() {
+            let substitute = previous[j] + usize::from(lhs_ch != rhs_ch);
+            let delete = previous[j + 1] + 1;
+            let insert = current[j] + 1;
+            current.push(substitute.min(delete).min(insert));
+        }
+        previous = current;
+    }
+
+    previous[rhs.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::Keywords;
+
+    const DIRECTIONS: Keywords<'static, u8> =
+        Keywords::new(&[("forward", 0), ("down", 1), ("up", 2)]);
+
+    #[test]
+    fn matches_keywords() {
+        assert_eq!(1, DIRECTIONS.parse("down").unwrap());
+        DIRECTIONS.parse("Down").unwrap_err();
+        assert_eq!(1, DIRECTIONS.ignore_case().parse("Down").unwrap());
+    }
+
+    #[test]
+    fn suggests_close_matches() {
+        let error = DIRECTIONS.parse("forwad").unwrap_err();
+        assert_eq!(
+            "Invalid keyword \"forwad\" (did you mean \"forward\"?), expected one of \"forward\", \"down\", \"up\"",
+            error.to_string()
+        );
+
+        let error = DIRECTIONS.parse("sideways").unwrap_err();
+        assert_eq!(
+            "Invalid keyword \"sideways\", expected one of \"forward\", \"down\", \"up\"",
+            error.to_string()
+        );
+
+        assert_eq!(0, super::edit_distance("DOWN", "down", true));
+        assert_eq!(3, super::edit_distance("kitten", "sitting", false));
+    }
+}
@@ -0,0 +1,410 @@
+//! Utilities for parsing input files.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt::Debug,
+    fs::{self, File},
+    hash::Hash,
+    io::{BufRead, BufReader},
+    iter,
+    path::Path,
+    str::FromStr,
+};
+
+use eyre::Report;
+use itertools::Itertools;
+
+use crate::tools::StringTools;
+
+pub mod combinator;
+mod keyword;
+mod normalise;
+
+pub use keyword::Keywords;
+pub use normalise::{normalise, Change, Normaliser};
+
+/// Read a whole input file, cleaning up anything that would trip up the parsers, like CRLF line
+/// endings, a byte order mark at the start, or trailing whitespace. Anything that gets cleaned up is
+/// logged at the debug level. Every other function here that reads a file goes through this.
+pub fn read_input<P: AsRef<Path>>(input: P) -> eyre::Result<String> {
+    let raw = fs::read_to_string(input)?;
+    let (output, _changes) = normalise(&raw);
+    Ok(output)
+}
+
+/// The same as `read_input`, but fails if anything needed cleaning up, listing what it was.
+pub fn read_input_strict<P: AsRef<Path>>(input: P) -> eyre::Result<String> {
+    let raw = fs::read_to_string(&input)?;
+    let (output, changes) = normalise(&raw);
+    if !changes.is_empty() {
+        eyre::bail!(
+            "Input file {} needed normalising: {}",
+            input.as_ref().display(),
+            changes.iter().join(", ")
+        );
+    }
+
+    Ok(output)
+}
+
+/// Fetch data from a file, split it up line by line, and parse it. I'm hoping the input formats
+/// they give stay simple enough to be parsed by something like this function, but we'll have to
+/// see how we go.
+pub fn line_separated<T: FromStr, P: AsRef<Path>>(input: P) -> eyre::Result<Vec<T>>
+where
+    Report: From<T::Err>,
+{
+    parse_lines(&read_input(input)?)
+}
+
+/// The same as `line_separated`, but fails if the file needed normalising.
+pub fn line_separated_strict<T: FromStr, P: AsRef<Path>>(input: P) -> eyre::Result<Vec<T>>
+where
+    Report: From<T::Err>,
+{
+    parse_lines(&read_input_strict(input)?)
+}
+
+/// Fetch data from a file, split it up by comma delimiters, and parse it.
+pub fn comma_separated<T: FromStr, P: AsRef<Path>>(input: P) -> eyre::Result<Vec<T>>
+    where
+        Report: From<T::Err>,
+{
+    parse_commas(&read_input(input)?)
+}
+
+/// The same as `comma_separated`, but fails if the file needed normalising.
+pub fn comma_separated_strict<T: FromStr, P: AsRef<Path>>(input: P) -> eyre::Result<Vec<T>>
+where
+    Report: From<T::Err>,
+{
+    parse_commas(&read_input_strict(input)?)
+}
+
+fn parse_lines<T: FromStr>(raw: &str) -> eyre::Result<Vec<T>>
+where
+    Report: From<T::Err>,
+{
+    let output = raw
+        .lines()
+        .map(|line| line.trim())
+        .map(|line| line.parse::<T>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(output)
+}
+
+fn parse_commas<T: FromStr>(raw: &str) -> eyre::Result<Vec<T>>
+where
+    Report: From<T::Err>,
+{
+    let output = raw
+        .split(',')
+        .map(|item| item.trim())
+        .map(|item| item.parse::<T>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(output)
+}
+
+/// The same as `line_separated`, but the file is read and parsed lazily, one line at a time. Use this
+/// for the really big inputs that we'd rather not hold in memory all at once.
+pub fn line_separated_lazy<T: FromStr, P: AsRef<Path>>(
+    input: P,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<T>>>
+where
+    Report: From<T::Err>,
+{
+    let file = File::open(input)?;
+    Ok(lines_from_reader(BufReader::new(file)))
+}
+
+/// The same as `line_separated_lazy`, but gives an error for the first line that needed
+/// normalising.
+pub fn line_separated_lazy_strict<T: FromStr, P: AsRef<Path>>(
+    input: P,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<T>>>
+where
+    Report: From<T::Err>,
+{
+    let file = File::open(input)?;
+    Ok(lines_from_reader_strict(BufReader::new(file)))
+}
+
+/// The same as `comma_separated`, but the file is read and parsed lazily, one item at a time.
+pub fn comma_separated_lazy<T: FromStr, P: AsRef<Path>>(
+    input: P,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<T>>>
+where
+    Report: From<T::Err>,
+{
+    let file = File::open(input)?;
+    Ok(commas_from_reader(BufReader::new(file)))
+}
+
+/// The same as `comma_separated_lazy`, but gives an error for the first item that needed
+/// normalising.
+pub fn comma_separated_lazy_strict<T: FromStr, P: AsRef<Path>>(
+    input: P,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<T>>>
+where
+    Report: From<T::Err>,
+{
+    let file = File::open(input)?;
+    Ok(commas_from_reader_strict(BufReader::new(file)))
+}
+
+/// Lazily split anything readable into lines, and parse each one. See `line_separated_lazy`.
+pub fn lines_from_reader<T: FromStr, R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = eyre::Result<T>>
+where
+    Report: From<T::Err>,
+{
+    normalised_lines(reader, false).map(|line| parse_item(&line?))
+}
+
+/// The same as `lines_from_reader`, but gives an error for the first line that needed normalising.
+pub fn lines_from_reader_strict<T: FromStr, R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = eyre::Result<T>>
+where
+    Report: From<T::Err>,
+{
+    normalised_lines(reader, true).map(|line| parse_item(&line?))
+}
+
+/// Lazily split anything readable by comma delimiters, and parse each item. See
+/// `comma_separated_lazy`.
+pub fn commas_from_reader<T: FromStr, R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = eyre::Result<T>>
+where
+    Report: From<T::Err>,
+{
+    normalised_items(reader, false).map(|item| parse_item(&item?))
+}
+
+/// The same as `commas_from_reader`, but gives an error for the first item that needed
+/// normalising.
+pub fn commas_from_reader_strict<T: FromStr, R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = eyre::Result<T>>
+where
+    Report: From<T::Err>,
+{
+    normalised_items(reader, true).map(|item| parse_item(&item?))
+}
+
+/// Read lines one at a time and clean them up. This doesn't use `BufRead::lines`, since that would
+/// quietly throw away the `\r` in CRLF endings.
+fn normalised_lines<R: BufRead>(
+    reader: R,
+    strict: bool,
+) -> impl Iterator<Item = eyre::Result<String>> {
+    let mut normaliser = Normaliser::new();
+    reader.split(b'\n').map(move |line| {
+        let line = String::from_utf8(line?)?;
+        let output = normaliser.line(&line);
+        if strict && output.len() != line.len() {
+            eyre::bail!(
+                "Input needed normalising: {}",
+                normaliser.changes().iter().join(", ")
+            );
+        }
+        Ok(output.to_owned())
+    })
+}
+
+/// Read comma separated items one at a time and clean them up. An item can go over the end of a
+/// line, so the normaliser sorts out the line numbers, and it needs to know which item is the
+/// last one so the end of the file gets treated as the end of a line.
+fn normalised_items<R: BufRead>(
+    reader: R,
+    strict: bool,
+) -> impl Iterator<Item = eyre::Result<String>> {
+    let mut normaliser = Normaliser::new();
+    let mut items = reader.split(b',').peekable();
+    iter::from_fn(move || {
+        let item = items.next()?;
+        let last = items.peek().is_none();
+        Some(item.map_err(Report::from).and_then(|item| {
+            let item = String::from_utf8(item)?;
+            let output = normaliser.chunk(&item, last);
+            if strict && output.len() != item.len() {
+                eyre::bail!(
+                    "Input needed normalising: {}",
+                    normaliser.changes().iter().join(", ")
+                );
+            }
+            Ok(output)
+        }))
+    })
+}
+
+fn parse_item<T: FromStr>(item: &str) -> eyre::Result<T>
+where
+    Report: From<T::Err>,
+{
+    Ok(item.trim().parse::<T>()?)
+}
+
+/// Parse lines of rules like `AB -> C` into a map from each key to its value, using whatever
+/// delimiter sits between them. Blank lines are skipped. It's an error for a key to show up twice,
+/// and the error will point out whether the two rules agree with each other or not.
+pub fn mapping<K, V>(input: &str, delimiter: &str) -> eyre::Result<HashMap<K, V>>
+where
+    K: FromStr + Hash + Eq + Debug,
+    V: FromStr + PartialEq + Debug,
+    Report: From<K::Err> + From<V::Err>,
+{
+    mapping_from_line(input, delimiter, 1)
+}
+
+/// The same as `mapping`, for when the rules are only part of a file. The line numbers in any
+/// errors count from `first_line`, which is the line of the file that `input` starts on.
+pub fn mapping_from_line<K, V>(
+    input: &str,
+    delimiter: &str,
+    first_line: usize,
+) -> eyre::Result<HashMap<K, V>>
+where
+    K: FromStr + Hash + Eq + Debug,
+    V: FromStr + PartialEq + Debug,
+    Report: From<K::Err> + From<V::Err>,
+{
+    let mut output = HashMap::new();
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .zip(first_line..)
+        .filter(|(line, _)| !line.is_empty());
+
+    for (line, number) in lines {
+        let (key, value) = line
+            .split_parse::<K, V>(delimiter)
+            .map_err(|e| e.wrap_err(format!("Invalid rule on line {}: {:?}", number, line)))?;
+
+        match output.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(entry) if *entry.get() == value => {
+                eyre::bail!("Duplicate rule for {:?} on line {}", entry.key(), number)
+            }
+            Entry::Occupied(entry) => eyre::bail!(
+                "Conflicting rules for {:?} on line {}: {:?} and {:?}",
+                entry.key(),
+                number,
+                entry.get(),
+                value
+            ),
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::Cell,
+        io::{self, BufReader, Cursor, Read},
+        rc::Rc,
+    };
+
+    #[test]
+    fn parses_lazily() {
+        let lines = super::lines_from_reader::<u32, _>(Cursor::new("1\n 2 \n3\n"))
+            .collect::<eyre::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(vec![1, 2, 3], lines);
+
+        let items = super::commas_from_reader::<i32, _>(Cursor::new("\u{feff}3,-4,5\r\n"))
+            .collect::<eyre::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(vec![3, -4, 5], items);
+
+        let mut bad = super::lines_from_reader::<u32, _>(Cursor::new("1\nx\n3"));
+        assert!(bad.next().unwrap().is_ok());
+        assert!(bad.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn parses_lazily_and_strictly() {
+        let mut lines = super::lines_from_reader_strict::<u32, _>(Cursor::new("1\n2 \n3\n"));
+        assert_eq!(1, lines.next().unwrap().unwrap());
+        let error = lines.next().unwrap().unwrap_err();
+        assert_eq!(
+            "Input needed normalising: trimmed trailing whitespace from 1 lines (first on line 2)",
+            error.to_string()
+        );
+        assert_eq!(3, lines.next().unwrap().unwrap());
+
+        let mut items = super::commas_from_reader_strict::<u32, _>(Cursor::new("1,2\r\n"));
+        assert_eq!(1, items.next().unwrap().unwrap());
+        let error = items.next().unwrap().unwrap_err();
+        assert_eq!(
+            "Input needed normalising: converted 1 CRLF line endings (first on line 1)",
+            error.to_string()
+        );
+    }
+
+    /// Something to read from that keeps track of how much has been read so far.
+    struct Counting<'a> {
+        inner: &'a [u8],
+        read: Rc<Cell<usize>>,
+    }
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let count = self.inner.read(buf)?;
+            self.read.set(self.read.get() + count);
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn streams_comma_separated_items() {
+        let input = (0..1000)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let read = Rc::new(Cell::new(0));
+        let reader = BufReader::with_capacity(
+            16,
+            Counting {
+                inner: input.as_bytes(),
+                read: Rc::clone(&read),
+            },
+        );
+
+        let mut items = super::commas_from_reader::<u32, _>(reader);
+        assert_eq!(0, items.next().unwrap().unwrap());
+        assert!(
+            read.get() < 32,
+            "Read {} bytes for the first item",
+            read.get()
+        );
+        assert_eq!(999, items.last().unwrap().unwrap());
+        assert_eq!(input.len(), read.get());
+    }
+
+    #[test]
+    fn parses_mappings() {
+        let rules = super::mapping::<String, u32>("a -> 1\n\nb -> 2\n", " -> ").unwrap();
+        assert_eq!(Some(&2), rules.get("b"));
+
+        let error = super::mapping::<String, u32>("a -> 1\nb -> 2\na -> 1", " -> ").unwrap_err();
+        assert_eq!("Duplicate rule for \"a\" on line 3", error.to_string());
+
+        let error = super::mapping::<String, u32>("a -> 1\na -> 2", " -> ").unwrap_err();
+        assert_eq!("Conflicting rules for \"a\" on line 2: 1 and 2", error.to_string());
+
+        let error = super::mapping::<String, u32>("a -> 1\nb -> x", " -> ").unwrap_err();
+        assert_eq!("Invalid rule on line 2: \"b -> x\"", error.to_string());
+
+        let error = super::mapping_from_line::<String, u32>("\na -> x", " -> ", 2).unwrap_err();
+        assert_eq!("Invalid rule on line 3: \"a -> x\"", error.to_string());
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+
+/// Something that had to be cleaned up in an input file before it could be parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The file started with a UTF-8 byte order mark.
+    ByteOrderMark,
+    /// Some lines ended in `\r\n` rather than `\n`.
+    CarriageReturns { count: usize, first_line: usize },
+    /// Some lines had spaces or tabs on the end.
+    TrailingWhitespace { count: usize, first_line: usize },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::ByteOrderMark => write!(f, "removed a UTF-8 byte order mark"),
+            Change::CarriageReturns { count, first_line } => write!(
+                f,
+                "converted {} CRLF line endings (first on line {})",
+                count, first_line
+            ),
+            Change::TrailingWhitespace { count, first_line } => write!(
+                f,
+                "trimmed trailing whitespace from {} lines (first on line {})",
+                count, first_line
+            ),
+        }
+    }
+}
+
+/// Cleans up input files that have been through a browser or an editor on somebody else's machine,
+/// one line at a time, and keeps track of everything that it had to change.
+#[derive(Debug, Default)]
+pub struct Normaliser {
+    /// How many lines have been finished so far.
+    line: usize,
+    started: bool,
+    byte_order_mark: bool,
+    carriage_returns: Option<(usize, usize)>,
+    trailing_whitespace: Option<(usize, usize)>,
+}
+
+/// Count another instance of a change, remembering the line that it first happened on.
+fn record(counter: &mut Option<(usize, usize)>, line: usize, description: &str) {
+    match counter {
+        Some((count, _)) => *count += 1,
+        None => {
+            log::debug!("Normalising input: {} (first on line {})", description, line);
+            *counter = Some((1, line));
+        }
+    }
+}
+
+impl Normaliser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clean up the next line of the input, which should not include the `\n` at the end.
+    pub fn line<'a>(&mut self, line: &'a str) -> &'a str {
+        self.clean(line, true)
+    }
+
+    /// Clean up the next chunk of an input that's been split on something other than newlines,
+    /// like an item of a comma separated file. The chunk can have any number of newlines in it, and
+    /// only the last chunk of the input should be marked as `last`.
+    pub fn chunk(&mut self, chunk: &str, last: bool) -> String {
+        let mut pieces = chunk.split('\n').peekable();
+        let mut output = Vec::new();
+        while let Some(piece) = pieces.next() {
+            let ends_line = last || pieces.peek().is_some();
+            output.push(self.clean(piece, ends_line));
+        }
+        output.join("\n")
+    }
+
+    /// Clean up part of a line, where only the end of a line can have a CR or trailing whitespace.
+    fn clean<'a>(&mut self, piece: &'a str, ends_line: bool) -> &'a str {
+        let line_number = self.line + 1;
+        let mut piece = piece;
+
+        if !self.started {
+            self.started = true;
+            if let Some(rest) = piece.strip_prefix('\u{feff}') {
+                log::debug!("Normalising input: removed a byte order mark");
+                self.byte_order_mark = true;
+                piece = rest;
+            }
+        }
+
+        if !ends_line {
+            return piece;
+        }
+        self.line += 1;
+
+        if let Some(rest) = piece.strip_suffix('\r') {
+            record(&mut self.carriage_returns, line_number, "converted CRLF line endings");
+            piece = rest;
+        }
+
+        let trimmed = piece.trim_end_matches([' ', '\t']);
+        if trimmed.len() != piece.len() {
+            record(&mut self.trailing_whitespace, line_number, "trimmed trailing whitespace");
+        }
+
+        trimmed
+    }
+
+    /// Everything that's been changed so far.
+    pub fn changes(&self) -> Vec<Change> {
+        let mut output = Vec::new();
+        if self.byte_order_mark {
+            output.push(Change::ByteOrderMark);
+        }
+        if let Some((count, first_line)) = self.carriage_returns {
+            output.push(Change::CarriageReturns { count, first_line });
+        }
+        if let Some((count, first_line)) = self.trailing_whitespace {
+            output.push(Change::TrailingWhitespace { count, first_line });
+        }
+        output
+    }
+}
+
+/// Clean up a whole input, returning the cleaned version along with everything that was changed.
+pub fn normalise(raw: &str) -> (String, Vec<Change>) {
+    let mut normaliser = Normaliser::new();
+    let output = raw
+        .split('\n')
+        .map(|line| normaliser.line(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (output, normaliser.changes())
+}
+
+#[cfg(test)]
+mod test {
+    use super::Change;
+
+    #[test]
+    fn cleans_up_inputs() {
+        let (output, changes) = super::normalise("\u{feff}1,2 \r\n\r\n3\t\r\n");
+        assert_eq!("1,2\n\n3\n", output);
+        assert_eq!(
+            vec![
+                Change::ByteOrderMark,
+                Change::CarriageReturns { count: 3, first_line: 1 },
+                Change::TrailingWhitespace { count: 2, first_line: 1 },
+            ],
+            changes
+        );
+
+        let (output, changes) = super::normalise("1\n2\n");
+        assert_eq!("1\n2\n", output);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn cleans_up_chunks() {
+        // The chunks of "\u{feff}1,2 \r\n3,4\t" split on commas, where only the ends of lines
+        // get cleaned up
+        let mut normaliser = super::Normaliser::new();
+        assert_eq!("1", normaliser.chunk("\u{feff}1", false));
+        assert_eq!("2\n3", normaliser.chunk("2 \r\n3", false));
+        assert_eq!("4", normaliser.chunk("4\t", true));
+        assert_eq!(
+            vec![
+                Change::ByteOrderMark,
+                Change::CarriageReturns { count: 1, first_line: 1 },
+                Change::TrailingWhitespace { count: 2, first_line: 1 },
+            ],
+            normaliser.changes()
+        );
+
+        let mut normaliser = super::Normaliser::new();
+        assert_eq!(" 1", normaliser.chunk(" 1", false));
+        assert_eq!("2\n\n3", normaliser.chunk("2\n\r\n3", false));
+        assert_eq!(
+            vec![Change::CarriageReturns { count: 1, first_line: 2 }],
+            normaliser.changes()
+        );
+    }
+}
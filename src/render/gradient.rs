@@ -0,0 +1,79 @@
+/// A red, green and blue colour.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const WHITE: Colour = Colour(255, 255, 255);
+
+    /// The colour that's `fraction` of the way from this one to another.
+    pub fn blend(self, other: Colour, fraction: f64) -> Colour {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * fraction).round() as u8;
+        Colour(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+/// A smooth run of colours, for turning numbers into something you can see. The stops are spread
+/// out evenly, and anything between two stops is blended from them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Gradient<'a>(pub &'a [Colour]);
+
+impl Gradient<'static> {
+    pub const GREYSCALE: Gradient<'static> = Gradient(&[Colour::BLACK, Colour::WHITE]);
+
+    /// Black through red and yellow to white, like something heating up.
+    pub const HEAT: Gradient<'static> = Gradient(&[
+        Colour::BLACK,
+        Colour(255, 0, 0),
+        Colour(255, 255, 0),
+        Colour::WHITE,
+    ]);
+}
+
+impl<'a> Gradient<'a> {
+    /// The colour at a point along the gradient, where 0 is the first stop and 1 is the last.
+    /// Anything outside of that is clamped to the ends, and an empty gradient is all black.
+    pub fn at(&self, fraction: f64) -> Colour {
+        let last = match self.0.len() {
+            0 => return Colour::BLACK,
+            len => len - 1,
+        };
+        let position = fraction.clamp(0.0, 1.0) * last as f64;
+        let idx = (position.floor() as usize).min(last.saturating_sub(1));
+        match self.0.get(idx + 1) {
+            Some(&next) => self.0[idx].blend(next, position - idx as f64),
+            None => self.0[idx],
+        }
+    }
+
+    /// The colour for a value, where `min` maps to the start of the gradient and `max` to the end.
+    pub fn scaled(&self, value: f64, min: f64, max: f64) -> Colour {
+        if max > min {
+            self.at((value - min) / (max - min))
+        } else {
+            self.at(0.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Colour, Gradient};
+
+    #[test]
+    fn blends_colours() {
+        assert_eq!(Colour::BLACK, Gradient::HEAT.at(-1.0));
+        assert_eq!(Colour(255, 0, 0), Gradient::HEAT.scaled(3.0, 0.0, 9.0));
+        assert_eq!(Colour(255, 128, 0), Gradient::HEAT.at(0.5));
+        assert_eq!(Colour::WHITE, Gradient::HEAT.at(1.0));
+        assert_eq!(
+            Colour(128, 128, 128),
+            Gradient::GREYSCALE.scaled(5.0, 0.0, 10.0)
+        );
+        assert_eq!(Colour(1, 2, 3), Gradient(&[Colour(1, 2, 3)]).at(0.7));
+    }
+}
@@ -0,0 +1,88 @@
+//! Binary PBM, PGM and PPM images. Each writer flushes at the end, since a `BufWriter` that's only
+//! flushed when it's dropped throws away any error from the last write.
+
+use super::Colour;
+use crate::data::Grid;
+use std::io::Write;
+
+/// Write a grid out as a black and white PBM image, where lit cells are black.
+pub fn write_pbm<T>(
+    grid: &Grid<T>,
+    mut lit: impl FnMut(&T) -> bool,
+    mut writer: impl Write,
+) -> eyre::Result<()> {
+    write!(writer, "P4\n{} {}\n", grid.width(), grid.height())?;
+    for row in grid.rows() {
+        // Each row is packed into bits separately, so the last byte of a row gets padded out
+        let bytes = row
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, cell)| lit(cell))
+                    .fold(0u8, |byte, (idx, _)| byte | (0x80 >> idx))
+            })
+            .collect::<Vec<_>>();
+        writer.write_all(&bytes)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a grid out as a greyscale PGM image, where 0 is black and 255 is white.
+pub fn write_pgm<T>(
+    grid: &Grid<T>,
+    f: impl FnMut(&T) -> u8,
+    mut writer: impl Write,
+) -> eyre::Result<()> {
+    write!(writer, "P5\n{} {}\n255\n", grid.width(), grid.height())?;
+    writer.write_all(&grid.values().map(f).collect::<Vec<_>>())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a grid out as a full colour PPM image. `Gradient` is handy for picking the colours.
+pub fn write_ppm<T>(
+    grid: &Grid<T>,
+    f: impl FnMut(&T) -> Colour,
+    mut writer: impl Write,
+) -> eyre::Result<()> {
+    write!(writer, "P6\n{} {}\n255\n", grid.width(), grid.height())?;
+    let bytes = grid
+        .values()
+        .map(f)
+        .flat_map(|Colour(r, g, b)| [r, g, b])
+        .collect::<Vec<_>>();
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::Colour;
+    use crate::data::Grid;
+
+    #[test]
+    fn writes_images() {
+        let grid = Grid::from_fn(9, 2, |coord| coord.0 % 4 == 0);
+
+        let mut pbm = Vec::new();
+        super::write_pbm(&grid, |&lit| lit, &mut pbm).unwrap();
+        assert_eq!(b"P4\n9 2\n\x88\x80\x88\x80", &pbm[..]);
+
+        let mut pgm = Vec::new();
+        super::write_pgm(&grid, |&lit| if lit { 0 } else { 255 }, &mut pgm).unwrap();
+        assert_eq!(11 + 18, pgm.len());
+
+        let mut ppm = Vec::new();
+        super::write_ppm(&grid, |_| Colour(1, 2, 3), &mut ppm).unwrap();
+        assert_eq!(b"P6\n9 2\n255\n\x01\x02\x03", &ppm[..14]);
+        assert_eq!(11 + 18 * 3, ppm.len());
+
+        let mut empty = Vec::new();
+        super::write_pbm(&Grid::new(0, 0, true), |&lit| lit, &mut empty).unwrap();
+        assert_eq!(b"P4\n0 0\n", &empty[..]);
+    }
+}
@@ -0,0 +1,62 @@
+//! Ways of looking at a grid: as plain text, as compact Unicode blocks for the terminal, or as
+//! image files. Everything works off a `Grid`, so sets of points go through `plot` first.
+
+mod gradient;
+mod image;
+mod text;
+
+pub use gradient::{Colour, Gradient};
+pub use image::{write_pbm, write_pgm, write_ppm};
+pub use text::{ascii, braille, half_blocks};
+
+use crate::data::{Coordinate, Grid, Point};
+use std::collections::HashMap;
+
+/// Turn a set of points into a grid of which cells are lit, just big enough to hold all of them.
+/// The smallest `x` and `y` end up on the left and top edges.
+pub fn plot(points: impl IntoIterator<Item = Point>) -> Grid<bool> {
+    let points = points
+        .into_iter()
+        .map(|point| (point, true))
+        .collect::<HashMap<_, _>>();
+    plot_values(&points, false)
+}
+
+/// The same as `plot`, but each point has a value, and any cells without one are filled in with
+/// the background.
+pub fn plot_values<T: Clone>(values: &HashMap<Point, T>, background: T) -> Grid<T> {
+    let min_x = values.keys().map(|point| point.0).min().unwrap_or(0);
+    let min_y = values.keys().map(|point| point.1).min().unwrap_or(0);
+    let max_x = values.keys().map(|point| point.0).max().unwrap_or(-1);
+    let max_y = values.keys().map(|point| point.1).max().unwrap_or(-1);
+
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+    Grid::from_fn(width, height, |Coordinate(x, y)| {
+        let point = Point(min_x + x as isize, min_y + y as isize);
+        values.get(&point).unwrap_or(&background).clone()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::plot;
+    use crate::data::{Grid, Point};
+
+    #[test]
+    fn plots_points() {
+        let grid = plot(vec![Point(-1, 3), Point(1, 4)]);
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(
+            "#..\n..#\n",
+            super::ascii(&grid, |&lit| if lit { '#' } else { '.' })
+        );
+
+        let empty = plot(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!("", super::ascii(&empty, |_| '#'));
+        assert_eq!("", empty.to_string());
+        assert_eq!("\n\n", super::ascii(&Grid::new(0, 2, false), |_| '#'));
+    }
+}
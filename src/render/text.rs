@@ -0,0 +1,74 @@
+use crate::data::{Coordinate, Grid};
+
+/// Draw a grid as text, one row per line, using a function to pick the character for each cell.
+pub fn ascii<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> char) -> String {
+    let mut output = String::with_capacity((grid.width() + 1) * grid.height());
+    for row in grid.rows() {
+        output.extend(row.iter().map(&mut f));
+        output.push('\n');
+    }
+    output
+}
+
+/// Draw a grid of lit and unlit cells using the Unicode half blocks, which fits two rows into each
+/// line of text. Terminal characters are about twice as tall as they are wide, so this also comes
+/// out looking roughly square.
+pub fn half_blocks<T>(grid: &Grid<T>, mut lit: impl FnMut(&T) -> bool) -> String {
+    let mut is_lit = |x, y| grid.get(Coordinate(x, y)).is_some_and(&mut lit);
+    let mut output = String::new();
+    for y in (0..grid.height()).step_by(2) {
+        for x in 0..grid.width() {
+            output.push(match (is_lit(x, y), is_lit(x, y + 1)) {
+                (false, false) => ' ',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (true, true) => '█',
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Draw a grid of lit and unlit cells using Unicode braille, which fits a two by four block of
+/// cells into each character. It's the most compact of the lot, but it can be a bit hard to read.
+pub fn braille<T>(grid: &Grid<T>, mut lit: impl FnMut(&T) -> bool) -> String {
+    // The bit for each dot within a character, indexed by [y][x]. The bottom row was added to
+    // braille later on, which is why it doesn't follow the pattern.
+    const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    let mut is_lit = |x, y| grid.get(Coordinate(x, y)).is_some_and(&mut lit);
+    let mut output = String::new();
+    for y in (0..grid.height()).step_by(4) {
+        for x in (0..grid.width()).step_by(2) {
+            let mut bits = 0;
+            for (dy, row) in DOTS.iter().enumerate() {
+                for (dx, bit) in row.iter().enumerate() {
+                    if is_lit(x + dx, y + dy) {
+                        bits |= bit;
+                    }
+                }
+            }
+            // Every value in the block is a valid character, so this can't fail
+            output.push(char::from_u32(0x2800 + bits).unwrap());
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data::Grid;
+
+    #[test]
+    fn draws_text() {
+        let grid = "101\n011\n110\n".parse::<Grid<u8>>().unwrap();
+        assert_eq!(
+            "x.x\n.xx\nxx.\n",
+            super::ascii(&grid, |&v| if v == 1 { 'x' } else { '.' })
+        );
+        assert_eq!("▀▄█\n▀▀ \n", super::half_blocks(&grid, |&v| v == 1));
+        assert_eq!("⠵⠃\n", super::braille(&grid, |&v| v == 1));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::data::{Boundary, Coordinate, Grid, Offsets, Point};
+use std::{collections::VecDeque, mem};
+
+/// A grid of cells that all change at once, where each cell's next value comes from a rule that
+/// can see the cell and its neighbours. The next generation is worked out into a second grid, so
+/// the rule always sees the previous generation however the cells are visited.
+pub struct Automaton<'a, T, R> {
+    current: Grid<T>,
+    next: Grid<T>,
+    offsets: Offsets<'a>,
+    boundary: Boundary,
+    rule: R,
+    reaction: Option<Box<dyn 'a + ChainReaction<T>>>,
+    generation: usize,
+}
+
+/// Some puzzles have cells that go off once the rule has been applied, setting off their
+/// neighbours, which might set off their neighbours, and so on (like day 11's flashing octopuses).
+/// Each cell can only be triggered once per step.
+pub trait ChainReaction<T> {
+    /// Whether a cell has been set off.
+    fn is_triggered(&self, value: &T) -> bool;
+
+    /// A neighbouring cell has been set off, so pass its energy onto this one.
+    fn pulse(&mut self, value: &mut T);
+
+    /// Once everything has calmed down, this is called for each cell that was set off.
+    fn settle(&mut self, _value: &mut T) {}
+}
+
+/// What happened during a step. See `Automaton::step`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct StepStats {
+    /// The generation that the step finished on, so the first step gives generation 1.
+    pub generation: usize,
+    /// How many cells have a different value to before the step.
+    pub changed: usize,
+    /// How many cells were set off by the chain reaction (if there is one).
+    pub triggered: usize,
+}
+
+/// A cell, and everything around it, as seen by a rule. See `Automaton::new`.
+#[derive(Debug, Clone)]
+pub struct Neighbourhood<'a, T> {
+    grid: &'a Grid<T>,
+    coord: Coordinate,
+    offsets: Offsets<'a>,
+    boundary: Boundary,
+    generation: usize,
+}
+
+impl<'a, T> Neighbourhood<'a, T> {
+    pub fn coord(&self) -> Coordinate {
+        self.coord
+    }
+
+    /// The cell's current value.
+    pub fn value(&self) -> &'a T {
+        &self.grid[self.coord]
+    }
+
+    /// The generation that's being worked out from, starting at 0.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The value of the cell at an offset from this one, whether or not it's one of the
+    /// neighbours. This is `None` if it's off the edge of the grid.
+    pub fn get(&self, delta: Point) -> Option<&'a T> {
+        let grid = self.grid;
+        self.coord
+            .step(delta, grid.max_point(), self.boundary)
+            .map(|coord| &grid[coord])
+    }
+
+    /// The values of the neighbours, in the same order as the offsets.
+    pub fn neighbours(&self) -> impl 'a + Iterator<Item = &'a T> {
+        let grid = self.grid;
+        grid.neighbours(self.coord, self.offsets, self.boundary)
+            .map(move |coord| &grid[coord])
+    }
+
+    /// The number of neighbours that match a condition, like the number that are alive.
+    pub fn count(&self, mut f: impl FnMut(&T) -> bool) -> usize {
+        self.neighbours().filter(|&value| f(value)).count()
+    }
+
+    /// Read the cells at each offset as the bits of a binary number, with the first offset being
+    /// the most significant bit and anything off the edge being a 0. This is good for rules that
+    /// look the next value up in a table.
+    pub fn pattern(&self, offsets: Offsets<'_>, mut f: impl FnMut(&T) -> bool) -> usize {
+        offsets.0.iter().fold(0, |acc, &delta| {
+            let bit = self.get(delta).is_some_and(&mut f);
+            (acc << 1) | bit as usize
+        })
+    }
+}
+
+impl<'a, T, R> Automaton<'a, T, R>
+where
+    T: Clone + PartialEq,
+    R: FnMut(&Neighbourhood<'_, T>) -> T,
+{
+    /// Set up a new automaton, where the offsets decide which cells are the neighbours, the
+    /// boundary decides what happens at the edges, and the rule works out a cell's next value.
+    pub fn new(grid: Grid<T>, offsets: Offsets<'a>, boundary: Boundary, rule: R) -> Self {
+        Automaton {
+            next: grid.clone(),
+            current: grid,
+            offsets,
+            boundary,
+            rule,
+            reaction: None,
+            generation: 0,
+        }
+    }
+
+    /// Add a chain reaction, which happens after the rule has been applied in each step. The
+    /// reaction spreads between the same neighbours as the rule uses.
+    pub fn with_reaction(mut self, reaction: impl 'a + ChainReaction<T>) -> Self {
+        self.reaction = Some(Box::new(reaction));
+        self
+    }
+
+    /// The current generation.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// How many steps have been run so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Move every cell on by one generation.
+    pub fn step(&mut self) -> StepStats {
+        for coord in self.current.coordinates() {
+            let neighbourhood = Neighbourhood {
+                grid: &self.current,
+                coord,
+                offsets: self.offsets,
+                boundary: self.boundary,
+                generation: self.generation,
+            };
+            self.next[coord] = (self.rule)(&neighbourhood);
+        }
+        mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+
+        let triggered = match self.reaction.as_mut() {
+            Some(reaction) => Self::react(
+                &mut self.current,
+                self.offsets,
+                self.boundary,
+                reaction.as_mut(),
+            ),
+            None => 0,
+        };
+
+        // The old generation is in the spare buffer now
+        let changed = self
+            .current
+            .values()
+            .zip(self.next.values())
+            .filter(|(new, old)| new != old)
+            .count();
+
+        StepStats {
+            generation: self.generation,
+            changed,
+            triggered,
+        }
+    }
+
+    /// Run lots of steps, giving back what happened in each of them.
+    pub fn run(&mut self, steps: usize) -> Vec<StepStats> {
+        (0..steps).map(|_| self.step()).collect()
+    }
+
+    /// Set off everything that's been triggered, returning how many cells went off.
+    fn react(
+        grid: &mut Grid<T>,
+        offsets: Offsets<'_>,
+        boundary: Boundary,
+        reaction: &mut dyn ChainReaction<T>,
+    ) -> usize {
+        let mut triggered = grid.map(|value| reaction.is_triggered(value));
+        let mut queue = triggered
+            .iter()
+            .filter(|&(_, &triggered)| triggered)
+            .map(|(coord, _)| coord)
+            .collect::<VecDeque<_>>();
+        let mut count = queue.len();
+
+        while let Some(current) = queue.pop_front() {
+            for neighbour in grid.neighbours(current, offsets, boundary) {
+                if triggered[neighbour] {
+                    continue;
+                }
+                reaction.pulse(&mut grid[neighbour]);
+                if reaction.is_triggered(&grid[neighbour]) {
+                    triggered[neighbour] = true;
+                    queue.push_back(neighbour);
+                    count += 1;
+                }
+            }
+        }
+
+        for (coord, &was_triggered) in triggered.iter() {
+            if was_triggered {
+                reaction.settle(&mut grid[coord]);
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Automaton, Neighbourhood};
+    use crate::{
+        data::{Boundary, Grid, Offsets, Point},
+        simulation::find_cycle,
+        tools::StringTools,
+    };
+
+    fn parse(picture: &str) -> Grid<bool> {
+        picture
+            .dedent()
+            .parse::<Grid<char>>()
+            .unwrap()
+            .map(|&cell| cell == '#')
+    }
+
+    fn life(cell: &Neighbourhood<'_, bool>) -> bool {
+        matches!(
+            (cell.value(), cell.count(|&alive| alive)),
+            (true, 2) | (_, 3)
+        )
+    }
+
+    #[test]
+    fn plays_life() {
+        let blinker = parse(
+            r"
+            .....
+            ..#..
+            ..#..
+            ..#..
+            .....
+            ",
+        );
+        let mut automaton = Automaton::new(blinker.clone(), Offsets::ALL, Boundary::Clip, life);
+        let stats = automaton.step();
+        assert_eq!(
+            (1, 4, 0),
+            (stats.generation, stats.changed, stats.triggered)
+        );
+        assert_eq!(
+            ".....\n.....\n.###.\n.....\n.....\n",
+            crate::render::ascii(automaton.grid(), |&alive| if alive { '#' } else { '.' })
+        );
+
+        // A glider wraps all the way around a torus in 4 * size steps
+        let glider = parse(
+            r"
+            .#....
+            ..#...
+            ###...
+            ......
+            ......
+            ......
+            ",
+        );
+        let step = |grid: &Grid<bool>| {
+            let mut automaton = Automaton::new(grid.clone(), Offsets::ALL, Boundary::Wrap, life);
+            automaton.step();
+            automaton.into_grid()
+        };
+        let history = find_cycle(glider.clone(), step, 100).unwrap();
+        assert_eq!(0, history.cycle().start);
+        assert_eq!(24, history.cycle().length);
+    }
+
+    #[test]
+    fn moves_and_looks_things_up() {
+        // Everything moves one to the right, wrapping around
+        let movers = parse("#..#.\n");
+        let mut automaton = Automaton::new(movers, Offsets::MANHATTAN, Boundary::Wrap, |cell| {
+            *cell.get(Point(-1, 0)).unwrap()
+        });
+        automaton.step();
+        assert_eq!(parse(".#..#\n"), *automaton.grid());
+
+        // Rule 90, as a lookup table of the cells on the left, middle and right
+        const TABLE: [bool; 8] = [false, true, false, true, true, false, true, false];
+        let row = Offsets(&[Point(-1, 0), Point(0, 0), Point(1, 0)]);
+        let seed = parse("...#...\n");
+        let mut automaton = Automaton::new(seed, row, Boundary::Clip, |cell| {
+            TABLE[cell.pattern(row, |&alive| alive)]
+        });
+        automaton.run(2);
+        assert_eq!(parse(".#...#.\n"), *automaton.grid());
+        assert_eq!(2, automaton.generation());
+    }
+}
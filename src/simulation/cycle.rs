@@ -0,0 +1,161 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a simulation starts repeating itself. After `start` steps, the state comes back around
+/// every `length` steps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step that has the same state as step `n`, which is always less than
+    /// `start + length`.
+    pub fn equivalent_step(self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Every state a simulation went through before it started repeating itself. See `find_cycle`.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    states: Vec<S>,
+    cycle: Cycle,
+}
+
+impl<S> History<S> {
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// The state after `n` steps, however big `n` is.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+
+    /// Every state up until the cycle comes back around, starting with the initial state.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Keep running `step` until a state comes up for the second time, remembering every state along
+/// the way so that any step can be looked up afterwards. Gives up with `None` if there's no repeat
+/// within `limit` steps.
+pub fn find_cycle<S: Eq + Hash + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> Option<History<S>> {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    for idx in 1..=limit {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start,
+                length: idx - start,
+            };
+            return Some(History { states, cycle });
+        }
+        seen.insert(next.clone(), idx);
+        states.push(next);
+    }
+
+    None
+}
+
+/// Find where a simulation starts repeating itself using Brent's algorithm, which only keeps a
+/// couple of states around at once - handy when the states are big, or when there are lots of them
+/// before the cycle starts. States don't need to be hashable, but the simulation gets run a few
+/// times over. Gives up with `None` if there's no repeat within roughly `limit` steps.
+pub fn find_cycle_brent<S: Eq + Clone>(
+    initial: &S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> Option<Cycle> {
+    // Find the length, by leaving the tortoise at each power of two and seeing how far the hare
+    // gets before it comes back around to it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    let mut steps = 1;
+    while tortoise != hare {
+        if steps > limit {
+            return None;
+        }
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+        steps += 1;
+    }
+
+    // Then find the start, by giving the hare a head start of one cycle and seeing where they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Work out the state after `n` steps, using a cycle to skip as much of the simulation as possible.
+pub fn state_at<S: Clone>(initial: &S, mut step: impl FnMut(&S) -> S, cycle: Cycle, n: usize) -> S {
+    let mut state = initial.clone();
+    for _ in 0..cycle.equivalent_step(n) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_cycle, find_cycle_brent, state_at, Cycle};
+
+    #[test]
+    fn finds_cycles() {
+        // 3, 9, 27, 81, 43, 29, 87, 61, 83, 49, 47, 41, 23, 69, 7, 21, 63, 89, 67, 1, 3, ...
+        let step = |&x: &u32| x * 3 % 100;
+        let history = find_cycle(3, step, 1000).unwrap();
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 20
+            },
+            history.cycle()
+        );
+        assert_eq!(&81, history.state_at(1_000_000_003));
+
+        // This one has a run up before it gets into the loop
+        let step = |&x: &u64| (x * x + 1) % 255;
+        let history = find_cycle(0, step, 1000).unwrap();
+        let cycle = find_cycle_brent(&0, step, 1000).unwrap();
+        assert_eq!(history.cycle(), cycle);
+        assert!(cycle.start > 0);
+
+        let far = 123_456_789_012;
+        assert_eq!(history.state_at(far), &state_at(&0, step, cycle, far));
+        let slow = (0..cycle.start + 3 * cycle.length + 2).fold(0, |x, _| step(&x));
+        assert_eq!(&slow, history.state_at(cycle.start + 3 * cycle.length + 2));
+
+        assert!(find_cycle(0u64, |x| x + 1, 100).is_none());
+        assert!(find_cycle_brent(&0u64, |x| x + 1, 100).is_none());
+    }
+}
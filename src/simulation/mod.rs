@@ -0,0 +1,7 @@
+//! Tools for puzzles that play out one step at a time.
+
+mod automaton;
+mod cycle;
+
+pub use automaton::{Automaton, ChainReaction, Neighbourhood, StepStats};
+pub use cycle::{find_cycle, find_cycle_brent, state_at, Cycle, History};
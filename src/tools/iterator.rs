@@ -7,6 +7,41 @@ pub trait MoreItertools: Iterator {
     fn parsed<'a, T>(self) -> Parsing<Self, T> where Self: Sized + Iterator<Item = &'a str> {
         Parsing { inner: self, _phantom: PhantomData }
     }
+
+    /// Collect exactly `N` items into an array, failing if there are more or less than that. This
+    /// stops as soon as it's seen one too many, so it's fine to use on endless iterators, but it
+    /// means the error can only say that there were at least `N + 1`.
+    fn collect_array<const N: usize>(self) -> eyre::Result<[Self::Item; N]>
+    where
+        Self: Sized,
+    {
+        to_array(self.take(N + 1).collect())
+    }
+
+    /// The same as `collect_array`, but for an iterator of results. Stops at the first error.
+    fn try_collect_array<T, E, const N: usize>(self) -> eyre::Result<[T; N]>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        eyre::Report: From<E>,
+    {
+        to_array(self.take(N + 1).collect::<Result<_, _>>()?)
+    }
+}
+
+/// Turn the first `N + 1` items of an iterator into an array, if there turned out to be exactly `N`.
+fn to_array<T, const N: usize>(items: Vec<T>) -> eyre::Result<[T; N]> {
+    let found = items.len();
+    <[T; N]>::try_from(items).map_err(|_| {
+        if found > N {
+            eyre::format_err!(
+                "Expected {} elements to collect, but found at least {}",
+                N,
+                found
+            )
+        } else {
+            eyre::format_err!("Expected {} elements to collect, but found {}", N, found)
+        }
+    })
 }
 
 /// See `MoreItertools::parsed`.
@@ -25,4 +60,40 @@ impl <'a, I, T> Iterator for Parsing<I, T> where I: Iterator<Item = &'a str>, T:
     }
 }
 
-impl<I: Iterator> MoreItertools for I {}
\ No newline at end of file
+impl<I: Iterator> MoreItertools for I {}
+
+#[cfg(test)]
+mod test {
+    use super::MoreItertools;
+
+    #[test]
+    fn collects_arrays() {
+        assert_eq!([1, 2, 3], (1..=3).collect_array().unwrap());
+
+        let error = (1..=5).collect_array::<3>().unwrap_err();
+        assert_eq!(
+            "Expected 3 elements to collect, but found at least 4",
+            error.to_string()
+        );
+        let error = (0..).collect_array::<3>().unwrap_err();
+        assert_eq!(
+            "Expected 3 elements to collect, but found at least 4",
+            error.to_string()
+        );
+        let error = (1..=2).collect_array::<3>().unwrap_err();
+        assert_eq!("Expected 3 elements to collect, but found 2", error.to_string());
+
+        let parsed: [u32; 2] = "4 5".split(' ').parsed().try_collect_array().unwrap();
+        assert_eq!([4, 5], parsed);
+        "4 x".split(' ').parsed::<u32>().try_collect_array::<_, _, 2>().unwrap_err();
+        // Anything after one too many isn't looked at, even if it's an error
+        let error = ["1", "2", "3", "x"]
+            .into_iter()
+            .parsed::<u32>()
+            .try_collect_array::<_, _, 2>();
+        assert_eq!(
+            "Expected 2 elements to collect, but found at least 3",
+            error.unwrap_err().to_string()
+        );
+    }
+}
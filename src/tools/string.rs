@@ -15,6 +15,13 @@ pub trait StringTools<'a> {
         A: FromStr,
         B: FromStr,
         eyre::Report: From<A::Err> + From<B::Err>;
+
+    /// Tidy up an example input that's been pasted into an indented raw string. Unlike
+    /// `lines_good`, this only removes the indentation that every line has in common, so any
+    /// whitespace that actually means something is kept. A blank first and last line (from the
+    /// opening and closing quotes) are removed too, and every line ends in a newline like it would
+    /// in a real input file.
+    fn dedent(self) -> String;
 }
 
 /// See `StringTools::lines_good`.
@@ -58,4 +65,49 @@ impl<'a> StringTools<'a> for &'a str {
 
         Ok((a, b))
     }
+
+    fn dedent(self) -> String {
+        let is_blank = |line: &str| line.trim().is_empty();
+        let indentation = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+
+        let mut lines = self.lines().collect::<Vec<_>>();
+        if lines.first().copied().is_some_and(is_blank) {
+            lines.remove(0);
+        }
+        if lines.last().copied().is_some_and(is_blank) {
+            lines.pop();
+        }
+
+        let common = lines
+            .iter()
+            .copied()
+            .filter(|line| !is_blank(line))
+            .map(indentation)
+            .min()
+            .unwrap_or(0);
+
+        lines
+            .into_iter()
+            .map(|line| if is_blank(line) { "" } else { &line[common..] })
+            .flat_map(|line| [line, "\n"])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StringTools;
+
+    #[test]
+    fn dedent_keeps_meaningful_whitespace() {
+        let raw = r"
+            22 13 17 11  0
+             8  2 23  4 24
+
+              #.
+        ";
+        assert_eq!("22 13 17 11  0\n 8  2 23  4 24\n\n  #.\n", raw.dedent());
+        assert_eq!("", "".dedent());
+        assert_eq!("a\n", "a".dedent());
+    }
 }